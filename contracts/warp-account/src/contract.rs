@@ -1,14 +1,16 @@
-use crate::state::CONFIG;
+use crate::state::{CONFIG, MSG_RESULTS, PENDING_MSGS};
 use crate::ContractError;
 use account::{
-    Config, ExecuteMsg, IbcTransferMsg, InstantiateMsg, MigrateMsg, QueryMsg, TimeoutBlock,
-    WithdrawAssetsMsg,
+    Config, ExecuteMsg, GenericWithPolicyMsg, IbcTransferMsg, InstantiateMsg, MigrateMsg,
+    PolicyMsg, QueryMsg, TimeoutBlock, WithdrawAssetsMsg,
+};
+use controller::account::{
+    AssetInfo, Cw721ExecuteMsg, MsgExecutionSummary, MsgPolicy, MsgResult, MsgStatus,
 };
-use controller::account::{AssetInfo, Cw721ExecuteMsg};
 use cosmwasm_std::CosmosMsg::Stargate;
 use cosmwasm_std::{
     entry_point, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, Uint128, WasmMsg,
+    Reply, Response, StdError, StdResult, SubMsg, SubMsgResult, Uint128, WasmMsg,
 };
 use cw20::{BalanceResponse, Cw20ExecuteMsg};
 use cw721::{Cw721QueryMsg, OwnerOfResponse};
@@ -51,6 +53,7 @@ pub fn execute(
         ExecuteMsg::Generic(data) => Ok(Response::new()
             .add_messages(data.msgs)
             .add_attribute("action", "generic")),
+        ExecuteMsg::GenericWithPolicy(data) => generic_with_policy(deps, data),
         ExecuteMsg::WithdrawAssets(data) => withdraw_assets(deps, env, info, data),
         ExecuteMsg::IbcTransfer(data) => ibc_transfer(deps, env, info, data),
     }
@@ -66,34 +69,131 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let msgs = PENDING_MSGS.load(deps.storage)?;
+    let mut results = MSG_RESULTS.load(deps.storage)?;
+
+    let index = msg.id as usize;
+    let policy = msgs
+        .get(index)
+        .map(|msg| msg.policy.clone())
+        .ok_or_else(|| StdError::generic_err(format!("unknown reply id {}", msg.id)))?;
+
+    let stop = match msg.result {
+        SubMsgResult::Ok(_) => {
+            results.push(MsgResult {
+                index: index as u32,
+                status: MsgStatus::Executed,
+                error: None,
+            });
+            false
+        }
+        SubMsgResult::Err(e) => {
+            if policy == MsgPolicy::Required {
+                return Err(ContractError::RequiredMsgFailed {
+                    index: index as u32,
+                    msg: e,
+                });
+            }
+            results.push(MsgResult {
+                index: index as u32,
+                status: MsgStatus::Failed,
+                error: Some(e),
+            });
+            policy == MsgPolicy::StopOnError
+        }
+    };
+
+    if !stop && index + 1 < msgs.len() {
+        MSG_RESULTS.save(deps.storage, &results)?;
+        return Ok(Response::new().add_submessage(policy_submsg(&msgs, index + 1)));
+    }
+
+    finish_policy_execution(deps, results, msgs.len())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     Ok(Response::new())
 }
 
+// msgs are dispatched one at a time so a failure can stop the ones after it. A msg calling back
+// into the account can't start another policy execution, it would overwrite this one's state
+pub fn generic_with_policy(
+    deps: DepsMut,
+    data: GenericWithPolicyMsg,
+) -> Result<Response, ContractError> {
+    if PENDING_MSGS.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::PolicyExecutionInProgress {});
+    }
+
+    if data.msgs.is_empty() {
+        return finish_policy_execution(deps, vec![], 0);
+    }
+
+    PENDING_MSGS.save(deps.storage, &data.msgs)?;
+    MSG_RESULTS.save(deps.storage, &vec![])?;
+
+    Ok(Response::new()
+        .add_submessage(policy_submsg(&data.msgs, 0))
+        .add_attribute("action", "generic_with_policy"))
+}
+
+fn policy_submsg(msgs: &[PolicyMsg], index: usize) -> SubMsg {
+    SubMsg::reply_always(msgs[index].msg.clone(), index as u64)
+}
+
+// msgs that never ran are reported as skipped
+fn finish_policy_execution(
+    deps: DepsMut,
+    mut results: Vec<MsgResult>,
+    len: usize,
+) -> Result<Response, ContractError> {
+    for index in results.len()..len {
+        results.push(MsgResult {
+            index: index as u32,
+            status: MsgStatus::Skipped,
+            error: None,
+        });
+    }
+
+    PENDING_MSGS.remove(deps.storage);
+    MSG_RESULTS.remove(deps.storage);
+
+    let failed = results
+        .iter()
+        .filter(|result| result.status == MsgStatus::Failed)
+        .count();
+
+    Ok(Response::new()
+        .set_data(to_binary(&MsgExecutionSummary { results })?)
+        .add_attribute("action", "generic_with_policy_result")
+        .add_attribute("msgs_failed", failed.to_string()))
+}
+
 pub fn ibc_transfer(
     _deps: DepsMut,
     env: Env,
     _info: MessageInfo,
     msg: IbcTransferMsg,
 ) -> Result<Response, ContractError> {
-    let mut transfer_msg = msg.transfer_msg.clone();
+    let mut transfer_msg = msg.transfer_msg;
 
-    if msg.timeout_block_delta.is_some() && msg.transfer_msg.timeout_block.is_some() {
-        let block = transfer_msg.timeout_block.unwrap();
+    if let (Some(timeout_block_delta), Some(block)) =
+        (msg.timeout_block_delta, transfer_msg.timeout_block.clone())
+    {
         transfer_msg.timeout_block = Some(TimeoutBlock {
             revision_number: Some(block.revision_number()),
-            revision_height: Some(env.block.height + msg.timeout_block_delta.unwrap()),
+            revision_height: Some(env.block.height + timeout_block_delta),
         })
     }
 
-    if msg.timeout_timestamp_seconds_delta.is_some() {
+    if let Some(timeout_timestamp_seconds_delta) = msg.timeout_timestamp_seconds_delta {
         transfer_msg.timeout_timestamp = Some(
             env.block
                 .time
-                .plus_seconds(
-                    env.block.time.seconds() + msg.timeout_timestamp_seconds_delta.unwrap(),
-                )
+                .plus_seconds(env.block.time.seconds() + timeout_timestamp_seconds_delta)
                 .nanos(),
         );
     }
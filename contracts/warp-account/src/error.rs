@@ -37,6 +37,12 @@ pub enum ContractError {
 
     #[error("Error resolving JSON path")]
     ResolveError {},
+
+    #[error("Required msg {index} failed: {msg}")]
+    RequiredMsgFailed { index: u32, msg: String },
+
+    #[error("A policy execution is already in progress.")]
+    PolicyExecutionInProgress {},
 }
 
 impl From<serde_json_wasm::de::Error> for ContractError {
@@ -1,4 +1,9 @@
-use account::Config;
+use account::{Config, PolicyMsg};
+use controller::account::MsgResult;
 use cw_storage_plus::Item;
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+// msgs of the policy execution in progress and the results collected so far
+pub const PENDING_MSGS: Item<Vec<PolicyMsg>> = Item::new("pending_msgs");
+pub const MSG_RESULTS: Item<Vec<MsgResult>> = Item::new("msg_results");
@@ -1,10 +1,14 @@
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, reply};
 use crate::ContractError;
-use account::{ExecuteMsg, GenericMsg, InstantiateMsg};
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use account::{ExecuteMsg, GenericMsg, GenericWithPolicyMsg, InstantiateMsg, PolicyMsg};
+use controller::account::{MsgExecutionSummary, MsgPolicy, MsgResult, MsgStatus};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
 use cosmwasm_std::{
-    to_binary, BankMsg, Coin, CosmosMsg, DistributionMsg, GovMsg, IbcMsg, IbcTimeout,
-    IbcTimeoutBlock, Response, StakingMsg, Uint128, VoteOption, WasmMsg,
+    coins, from_binary, to_binary, BankMsg, Coin, CosmosMsg, DistributionMsg, GovMsg, IbcMsg,
+    IbcTimeout, IbcTimeoutBlock, OwnedDeps, Reply, Response, StakingMsg, SubMsg, SubMsgResponse,
+    SubMsgResult, Uint128, VoteOption, WasmMsg,
 };
 
 #[test]
@@ -325,3 +329,169 @@ fn test_execute_unauth() {
 
     assert_eq!(execute_res, ContractError::Unauthorized {})
 }
+
+fn send_msg(to_address: &str) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: to_address.to_string(),
+        amount: coins(100, "coin"),
+    })
+}
+
+fn reply_msg(id: u64, error: Option<&str>) -> Reply {
+    Reply {
+        id,
+        result: match error {
+            None => SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+            Some(error) => SubMsgResult::Err(error.to_string()),
+        },
+    }
+}
+
+fn execute_with_policies(policies: Vec<MsgPolicy>) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vlad_controller", &[]),
+        InstantiateMsg {
+            owner: "vlad".to_string(),
+            funds: None,
+        },
+    )
+    .unwrap();
+
+    let msgs = policies
+        .into_iter()
+        .enumerate()
+        .map(|(index, policy)| PolicyMsg {
+            msg: send_msg(&format!("vlad{}", index)),
+            policy,
+        })
+        .collect();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vlad_controller", &[]),
+        ExecuteMsg::GenericWithPolicy(GenericWithPolicyMsg { msgs }),
+    )
+    .unwrap();
+
+    // only the first msg is dispatched up front
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(send_msg("vlad0"), 0)]
+    );
+
+    deps
+}
+
+#[test]
+fn test_execute_generic_with_policy() {
+    let mut deps = execute_with_policies(vec![
+        MsgPolicy::Required,
+        MsgPolicy::Optional,
+        MsgPolicy::StopOnError,
+        MsgPolicy::Optional,
+    ]);
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg(0, None)).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(send_msg("vlad1"), 1)]
+    );
+
+    // an optional msg failing does not stop the next one
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        reply_msg(1, Some("optional failed")),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(send_msg("vlad2"), 2)]
+    );
+
+    let res = reply(deps.as_mut(), mock_env(), reply_msg(2, Some("stop failed"))).unwrap();
+    assert!(res.messages.is_empty());
+
+    let summary: MsgExecutionSummary = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(
+        summary.results,
+        vec![
+            MsgResult {
+                index: 0,
+                status: MsgStatus::Executed,
+                error: None,
+            },
+            MsgResult {
+                index: 1,
+                status: MsgStatus::Failed,
+                error: Some("optional failed".to_string()),
+            },
+            MsgResult {
+                index: 2,
+                status: MsgStatus::Failed,
+                error: Some("stop failed".to_string()),
+            },
+            MsgResult {
+                index: 3,
+                status: MsgStatus::Skipped,
+                error: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_execute_generic_with_policy_required_failed() {
+    let mut deps = execute_with_policies(vec![MsgPolicy::Optional, MsgPolicy::Required]);
+
+    reply(deps.as_mut(), mock_env(), reply_msg(0, None)).unwrap();
+
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        reply_msg(1, Some("required failed")),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RequiredMsgFailed {
+            index: 1,
+            msg: "required failed".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_execute_generic_with_policy_reentry() {
+    let mut deps = execute_with_policies(vec![MsgPolicy::Optional, MsgPolicy::Required]);
+
+    // a dispatched msg calling back into the account while the batch is pending
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vlad", &[]),
+        ExecuteMsg::GenericWithPolicy(GenericWithPolicyMsg {
+            msgs: vec![PolicyMsg {
+                msg: send_msg("nested"),
+                policy: MsgPolicy::Optional,
+            }],
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PolicyExecutionInProgress {});
+
+    // the outer batch carries on untouched
+    let res = reply(deps.as_mut(), mock_env(), reply_msg(0, None)).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(send_msg("vlad1"), 1)]
+    );
+}
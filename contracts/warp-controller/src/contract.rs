@@ -1,16 +1,29 @@
-use crate::error::map_contract_error;
-use crate::state::{ACCOUNTS, CONFIG, FINISHED_JOBS, PENDING_JOBS};
+use crate::error::{is_out_of_gas_error, job_failure_reason, map_contract_error};
+use crate::state::{
+    ACCOUNTS, CONFIG, FINISHED_JOBS, JOB_DISPATCHES, JOB_INDEX_MIGRATION, KEEPERS, PENDING_JOBS,
+};
+use crate::util::asset::{
+    account_transfer_msg, job_reward_asset, query_asset_balance, transfer_msg,
+    validate_reward_assets,
+};
+use crate::util::history::{record_run, series_id};
+use crate::util::keeper::increment;
+use crate::util::limits::{finish_with_status, is_expired, max_executions_reached};
+use crate::util::output::capture_outputs;
+use crate::util::policy::msg_results;
+use crate::util::retry::{retries_left, retry_eligibility, should_retry};
+use crate::util::reward::{gas_allowance, gas_budget};
+use crate::util::schedule::{exclusivity_end, next_eligibility};
 use crate::{execute, query, state::STATE, ContractError};
-use account::{GenericMsg, WithdrawAssetsMsg};
+use account::WithdrawAssetsMsg;
 use controller::account::{Account, Fund, FundTransferMsgs, TransferFromMsg, TransferNftMsg};
 use controller::job::{Job, JobStatus};
 use cosmwasm_schema::cw_serde;
 
 use controller::{Config, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, State};
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Attribute, BalanceResponse, BankMsg, BankQuery, Binary, Coin,
-    CosmosMsg, Deps, DepsMut, Env, MessageInfo, QueryRequest, Reply, Response, StdError, StdResult,
-    SubMsgResult, Uint128, Uint64, WasmMsg,
+    entry_point, to_binary, Addr, Attribute, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Reply, Response, StdError, StdResult, SubMsgResult, Uint128, Uint64, WasmMsg,
 };
 use cw_storage_plus::Item;
 
@@ -44,6 +57,13 @@ pub fn instantiate(
         a_max: msg.a_max,
         a_min: msg.a_min,
         q_max: msg.q_max,
+        reward_assets: validate_reward_assets(deps.api, msg.reward_assets.unwrap_or_default())?,
+        max_job_gas_limit: msg.max_job_gas_limit,
+        keeper_min_stake: msg.keeper_min_stake.unwrap_or_default(),
+        keeper_slash_percentage: msg.keeper_slash_percentage.unwrap_or_default(),
+        keeper_unbonding_period: msg.keeper_unbonding_period.unwrap_or_default(),
+        finished_job_retention: msg.finished_job_retention,
+        prune_bounty: msg.prune_bounty.unwrap_or_default(),
     };
 
     if config.a_max < config.a_min {
@@ -66,6 +86,10 @@ pub fn instantiate(
         return Err(ContractError::CancellationFeeTooHigh {});
     }
 
+    if config.keeper_slash_percentage.u64() > 100 {
+        return Err(ContractError::SlashPercentageTooHigh {});
+    }
+
     STATE.save(deps.storage, &state)?;
     CONFIG.save(deps.storage, &config)?;
 
@@ -81,13 +105,25 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::CreateJob(data) => execute::job::create_job(deps, env, info, data),
+        ExecuteMsg::CreateJobs(data) => execute::job::create_jobs(deps, env, info, data),
         ExecuteMsg::DeleteJob(data) => execute::job::delete_job(deps, env, info, data),
         ExecuteMsg::UpdateJob(data) => execute::job::update_job(deps, env, info, data),
         ExecuteMsg::ExecuteJob(data) => execute::job::execute_job(deps, env, info, data),
         ExecuteMsg::EvictJob(data) => execute::job::evict_job(deps, env, info, data),
+        ExecuteMsg::PauseJob(data) => execute::job::pause_job(deps, env, info, data),
+        ExecuteMsg::ResumeJob(data) => execute::job::resume_job(deps, env, info, data),
+        ExecuteMsg::PruneFinishedJobs(data) => {
+            execute::job::prune_finished_jobs(deps, env, info, data)
+        }
 
         ExecuteMsg::CreateAccount(data) => execute::account::create_account(deps, env, info, data),
 
+        ExecuteMsg::BondKeeper(data) => execute::keeper::bond_keeper(deps, env, info, data),
+        ExecuteMsg::UnbondKeeper(data) => execute::keeper::unbond_keeper(deps, env, info, data),
+        ExecuteMsg::WithdrawKeeperStake(data) => {
+            execute::keeper::withdraw_keeper_stake(deps, env, info, data)
+        }
+
         ExecuteMsg::UpdateConfig(data) => execute::controller::update_config(deps, env, info, data),
 
         ExecuteMsg::MigrateAccounts(data) => {
@@ -99,6 +135,9 @@ pub fn execute(
         ExecuteMsg::MigrateFinishedJobs(data) => {
             execute::controller::migrate_finished_jobs(deps, env, info, data)
         }
+        ExecuteMsg::MigrateJobIndexes(data) => {
+            execute::controller::migrate_job_indexes(deps, env, info, data)
+        }
     }
 }
 
@@ -107,12 +146,28 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::QueryJob(data) => to_binary(&query::job::query_job(deps, env, data)?),
         QueryMsg::QueryJobs(data) => to_binary(&query::job::query_jobs(deps, env, data)?),
+        QueryMsg::QueryJobDependencies(data) => {
+            to_binary(&query::job::query_job_dependencies(deps, env, data)?)
+        }
+        QueryMsg::QueryJobRevisions(data) => {
+            to_binary(&query::job::query_job_revisions(deps, env, data)?)
+        }
+        QueryMsg::QueryJobSummary(data) => {
+            to_binary(&query::job::query_job_summary(deps, env, data)?)
+        }
+        QueryMsg::QueryJobHistory(data) => {
+            to_binary(&query::job::query_job_history(deps, env, data)?)
+        }
+        QueryMsg::SimulateJob(data) => to_binary(&query::job::query_simulate_job(deps, env, data)?),
 
         QueryMsg::QueryAccount(data) => to_binary(&query::account::query_account(deps, env, data)?),
         QueryMsg::QueryAccounts(data) => {
             to_binary(&query::account::query_accounts(deps, env, data)?)
         }
 
+        QueryMsg::QueryKeeper(data) => to_binary(&query::keeper::query_keeper(deps, env, data)?),
+        QueryMsg::QueryKeepers(data) => to_binary(&query::keeper::query_keepers(deps, env, data)?),
+
         QueryMsg::QueryConfig(data) => {
             to_binary(&query::controller::query_config(deps, env, data)?)
         }
@@ -182,9 +237,18 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
             a_max: v1_config.a_max,
             a_min: v1_config.a_min,
             q_max: v1_config.q_max,
+            reward_assets: vec![],
+            max_job_gas_limit: None,
+            keeper_min_stake: Uint128::zero(),
+            keeper_slash_percentage: Uint64::zero(),
+            keeper_unbonding_period: Uint64::zero(),
+            finished_job_retention: None,
+            prune_bounty: Uint128::zero(),
         },
     )?;
 
+    JOB_INDEX_MIGRATION.save(deps.storage, &None)?;
+
     Ok(Response::new())
 }
 
@@ -209,16 +273,16 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
             let owner = event
                 .attributes
                 .iter()
-                .cloned()
                 .find(|attr| attr.key == "owner")
+                .cloned()
                 .ok_or_else(|| StdError::generic_err("cannot find `owner` attribute"))?
                 .value;
 
             let address = event
                 .attributes
                 .iter()
-                .cloned()
                 .find(|attr| attr.key == "contract_addr")
+                .cloned()
                 .ok_or_else(|| StdError::generic_err("cannot find `contract_addr` attribute"))?
                 .value;
 
@@ -226,8 +290,8 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                 &event
                     .attributes
                     .iter()
-                    .cloned()
                     .find(|attr| attr.key == "funds")
+                    .cloned()
                     .ok_or_else(|| StdError::generic_err("cannot find `funds` attribute"))?
                     .value,
             )?;
@@ -236,18 +300,13 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                 &event
                     .attributes
                     .iter()
-                    .cloned()
                     .find(|attr| attr.key == "cw_funds")
+                    .cloned()
                     .ok_or_else(|| StdError::generic_err("cannot find `cw_funds` attribute"))?
                     .value,
             )?;
 
-            let cw_funds_vec = match cw_funds {
-                None => {
-                    vec![]
-                }
-                Some(funds) => funds,
-            };
+            let cw_funds_vec = cw_funds.unwrap_or_default();
 
             let mut msgs_vec: Vec<CosmosMsg> = vec![];
 
@@ -303,13 +362,186 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
         _ => {
             let mut state = STATE.load(deps.storage)?;
 
-            let new_status = match msg.result {
-                SubMsgResult::Ok(_) => JobStatus::Executed,
-                SubMsgResult::Err(_) => JobStatus::Failed,
+            let (new_status, failure_reason) = match &msg.result {
+                SubMsgResult::Ok(_) => (JobStatus::Executed, None),
+                SubMsgResult::Err(e) => (JobStatus::Failed, Some(job_failure_reason(e))),
             };
 
             let job = PENDING_JOBS().load(deps.storage, msg.id)?;
             PENDING_JOBS().remove(deps.storage, msg.id)?;
+            state.q = state.q.checked_sub(Uint64::new(1))?;
+
+            let config = CONFIG.load(deps.storage)?;
+            let reward_asset = job_reward_asset(&config, &job);
+            let owner_account = ACCOUNTS().load(deps.storage, job.owner.clone())?;
+
+            let mut msgs = vec![];
+            let mut gas_attrs = vec![];
+
+            let dispatch = JOB_DISPATCHES.may_load(deps.storage, msg.id)?;
+            JOB_DISPATCHES.remove(deps.storage, msg.id);
+            let keeper_address = dispatch.as_ref().map(|d| d.keeper.clone());
+
+            // pay the keeper's flat gas allowance out of the escrowed budget, the rest goes back to the owner
+            let budget = gas_budget(&job);
+            if !budget.is_zero() {
+                let allowance = match &keeper_address {
+                    Some(keeper_address) => {
+                        match ACCOUNTS().may_load(deps.storage, keeper_address.clone())? {
+                            Some(keeper_account) => {
+                                let allowance = gas_allowance(&config, &job);
+                                if !allowance.is_zero() {
+                                    msgs.push(transfer_msg(
+                                        &reward_asset,
+                                        allowance,
+                                        &keeper_account.account,
+                                    )?);
+                                }
+                                allowance
+                            }
+                            None => Uint128::zero(),
+                        }
+                    }
+                    None => Uint128::zero(),
+                };
+
+                let remainder = budget - allowance;
+                if !remainder.is_zero() {
+                    msgs.push(transfer_msg(
+                        &reward_asset,
+                        remainder,
+                        &owner_account.account,
+                    )?);
+                }
+
+                gas_attrs.push(Attribute::new("job_gas_allowance", allowance));
+                gas_attrs.push(Attribute::new("job_gas_budget_remainder", remainder));
+            }
+
+            if let Some(keeper_address) = keeper_address {
+                if let Some(mut keeper) = KEEPERS().may_load(deps.storage, keeper_address)? {
+                    match new_status {
+                        JobStatus::Executed => increment(&mut keeper.executed)?,
+                        _ => increment(&mut keeper.failed)?,
+                    }
+                    KEEPERS().save(deps.storage, keeper.address.clone(), &keeper)?;
+                }
+            }
+
+            let (mut res_attrs, run_error) = match &msg.result {
+                SubMsgResult::Err(e) => (
+                    vec![
+                        Attribute::new(
+                            "transaction_error",
+                            format!("{}. {}", e, map_contract_error(e)),
+                        ),
+                        Attribute::new("job_out_of_gas", is_out_of_gas_error(e).to_string()),
+                    ],
+                    Some(map_contract_error(e)),
+                ),
+                _ => (vec![], None),
+            };
+
+            let results = msg_results(&job, &msg);
+            if let Some(results) = &results {
+                res_attrs.push(Attribute::new(
+                    "job_msg_results",
+                    serde_json_wasm::to_string(results)?,
+                ));
+            }
+
+            // captured before the finished job is recorded, so a requeue applies update functions on top
+            let (vars, output_error) = capture_outputs(deps.as_ref(), &config, &job, &msg);
+            if let Some(output_error) = output_error {
+                res_attrs.push(Attribute::new("job_output_error", output_error));
+            }
+
+            let series_id = series_id(&job);
+
+            // a failed run with retries left goes back to the queue under the same id,
+            // the keeper was paid out of the escrow so it is refilled from the owner's account
+            let escrow = job.reward.checked_add(gas_budget(&job))?;
+            if new_status == JobStatus::Failed
+                && should_retry(&job, &env.block)
+                && query_asset_balance(&deps.querier, &reward_asset, &owner_account.account)?
+                    >= escrow
+            {
+                let eligible_at = retry_eligibility(&job.retry_policy, &env.block);
+                let exclusive_until = exclusivity_end(&job.exclusivity, &eligible_at, &env.block);
+
+                let retried_job = Job {
+                    id: job.id,
+                    owner: job.owner,
+                    last_update_time: Uint64::new(env.block.time.seconds()),
+                    name: job.name,
+                    description: job.description,
+                    labels: job.labels,
+                    status: JobStatus::Pending,
+                    condition: job.condition,
+                    terminate_condition: job.terminate_condition,
+                    msgs: job.msgs,
+                    vars: job.vars,
+                    recurring: job.recurring,
+                    requeue_on_evict: job.requeue_on_evict,
+                    reward: job.reward,
+                    assets_to_withdraw: job.assets_to_withdraw,
+                    schedule: job.schedule,
+                    eligible_at,
+                    max_executions: job.max_executions,
+                    expires_at: job.expires_at,
+                    // a retry is another attempt at the same run
+                    executions: job.executions,
+                    reward_asset: job.reward_asset,
+                    dependencies: job.dependencies,
+                    gas_limit: job.gas_limit,
+                    exclusivity: job.exclusivity,
+                    exclusive_until,
+                    reward_curve: job.reward_curve,
+                    gas_allowance: job.gas_allowance,
+                    finished_at: None,
+                    series_id,
+                    stable_id: job.stable_id,
+                    failure_reason: None,
+                    retry_policy: job.retry_policy,
+                    retries_left: job.retries_left.checked_sub(Uint64::one())?,
+                    msg_policies: job.msg_policies,
+                    msg_results: None,
+                    branches: job.branches,
+                    else_msgs: job.else_msgs,
+                    output_bindings: job.output_bindings,
+                };
+                PENDING_JOBS().save(deps.storage, msg.id, &retried_job)?;
+                state.q = state.q.checked_add(Uint64::new(1))?;
+                STATE.save(deps.storage, &state)?;
+
+                if let Some(dispatch) = dispatch {
+                    record_run(
+                        deps.storage,
+                        &retried_job,
+                        JobStatus::Failed,
+                        dispatch.keeper,
+                        &env.block,
+                        dispatch.reward,
+                        run_error,
+                    )?;
+                }
+
+                msgs.push(account_transfer_msg(
+                    &owner_account.account,
+                    &reward_asset,
+                    escrow,
+                    &env.contract.address,
+                )?);
+
+                return Ok(Response::new()
+                    .add_attribute("action", "execute_reply")
+                    .add_attribute("job_id", retried_job.id)
+                    .add_attributes(res_attrs)
+                    .add_attributes(gas_attrs)
+                    .add_attribute("sub_action", "retry_job")
+                    .add_attribute("job_retries_left", retried_job.retries_left)
+                    .add_messages(msgs));
+            }
 
             let finished_job = FINISHED_JOBS().update(deps.storage, msg.id, |j| match j {
                 None => Ok(Job {
@@ -323,44 +555,77 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                     condition: job.condition,
                     terminate_condition: job.terminate_condition,
                     msgs: job.msgs,
-                    vars: job.vars,
+                    vars,
                     recurring: job.recurring,
                     requeue_on_evict: job.requeue_on_evict,
                     reward: job.reward,
                     assets_to_withdraw: job.assets_to_withdraw,
+                    schedule: job.schedule,
+                    eligible_at: job.eligible_at,
+                    max_executions: job.max_executions,
+                    expires_at: job.expires_at,
+                    executions: job.executions.checked_add(Uint64::one())?,
+                    reward_asset: job.reward_asset,
+                    dependencies: job.dependencies,
+                    gas_limit: job.gas_limit,
+                    exclusivity: job.exclusivity,
+                    exclusive_until: job.exclusive_until,
+                    reward_curve: job.reward_curve,
+                    gas_allowance: job.gas_allowance,
+                    finished_at: Some(Uint64::new(env.block.time.seconds())),
+                    series_id,
+                    stable_id: job.stable_id,
+                    failure_reason,
+                    retry_policy: job.retry_policy,
+                    retries_left: job.retries_left,
+                    msg_policies: job.msg_policies,
+                    msg_results: results,
+                    branches: job.branches,
+                    else_msgs: job.else_msgs,
+                    output_bindings: job.output_bindings,
                 }),
                 Some(_) => Err(ContractError::JobAlreadyFinished {}),
             })?;
 
-            let res_attrs = match msg.result {
-                SubMsgResult::Err(e) => vec![Attribute::new(
-                    "transaction_error",
-                    format!("{}. {}", &e, map_contract_error(&e)),
-                )],
-                _ => vec![],
-            };
+            if let Some(dispatch) = dispatch {
+                record_run(
+                    deps.storage,
+                    &finished_job,
+                    finished_job.status.clone(),
+                    dispatch.keeper,
+                    &env.block,
+                    dispatch.reward,
+                    run_error,
+                )?;
+            }
 
-            let mut msgs = vec![];
             let mut new_job_attrs = vec![];
 
-            let account = ACCOUNTS().load(deps.storage, finished_job.owner.clone())?;
-            let config = CONFIG.load(deps.storage)?;
+            let account = owner_account;
 
             //assume reward.amount == warp token allowance
             let fee = finished_job.reward * Uint128::from(config.creation_fee_percentage)
                 / Uint128::new(100);
 
-            let account_amount = deps
-                .querier
-                .query::<BalanceResponse>(&QueryRequest::Bank(BankQuery::Balance {
-                    address: account.account.to_string(),
-                    denom: config.fee_denom.clone(),
-                }))?
-                .amount
-                .amount;
+            let account_amount =
+                query_asset_balance(&deps.querier, &reward_asset, &account.account)?;
 
             if finished_job.recurring {
-                if account_amount < fee + finished_job.reward {
+                if max_executions_reached(&finished_job) {
+                    finish_with_status(deps.storage, &finished_job, JobStatus::Completed)?;
+                    new_job_attrs.push(Attribute::new("action", "recur_job"));
+                    new_job_attrs.push(Attribute::new(
+                        "creation_status",
+                        "terminated_due_to_max_executions",
+                    ));
+                } else if is_expired(&finished_job, &env.block) {
+                    finish_with_status(deps.storage, &finished_job, JobStatus::Expired)?;
+                    new_job_attrs.push(Attribute::new("action", "recur_job"));
+                    new_job_attrs.push(Attribute::new(
+                        "creation_status",
+                        "terminated_due_to_expiration",
+                    ));
+                } else if account_amount < fee + finished_job.reward + budget {
                     new_job_attrs.push(Attribute::new("action", "recur_job"));
                     new_job_attrs.push(Attribute::new("creation_status", "failed_insufficient_fee"))
                 } else if !(finished_job.status == JobStatus::Executed
@@ -429,12 +694,24 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                     }
 
                     if !should_terminate_job {
-                        let new_job = PENDING_JOBS().update(
-                            deps.storage,
-                            state.current_job_id.u64(),
-                            |s| match s {
+                        let eligible_at = next_eligibility(&finished_job.schedule, &env.block)?;
+                        let exclusive_until =
+                            exclusivity_end(&finished_job.exclusivity, &eligible_at, &env.block);
+
+                        // a stable job goes back to the queue under its own id
+                        let new_job_id = if finished_job.stable_id {
+                            FINISHED_JOBS().remove(deps.storage, finished_job.id.u64())?;
+                            finished_job.id
+                        } else {
+                            let id = state.current_job_id;
+                            state.current_job_id = id.checked_add(Uint64::new(1))?;
+                            id
+                        };
+
+                        let new_job =
+                            PENDING_JOBS().update(deps.storage, new_job_id.u64(), |s| match s {
                                 None => Ok(Job {
-                                    id: state.current_job_id,
+                                    id: new_job_id,
                                     owner: finished_job.owner.clone(),
                                     last_update_time: Uint64::from(env.block.time.seconds()),
                                     name: finished_job.name.clone(),
@@ -449,51 +726,58 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                                     msgs: finished_job.msgs.clone(),
                                     reward: finished_job.reward,
                                     assets_to_withdraw: finished_job.assets_to_withdraw,
+                                    schedule: finished_job.schedule,
+                                    eligible_at,
+                                    max_executions: finished_job.max_executions,
+                                    expires_at: finished_job.expires_at,
+                                    executions: finished_job.executions,
+                                    reward_asset: finished_job.reward_asset,
+                                    dependencies: finished_job.dependencies,
+                                    gas_limit: finished_job.gas_limit,
+                                    exclusivity: finished_job.exclusivity,
+                                    exclusive_until,
+                                    reward_curve: finished_job.reward_curve,
+                                    gas_allowance: finished_job.gas_allowance,
+                                    finished_at: None,
+                                    series_id: finished_job.series_id,
+                                    stable_id: finished_job.stable_id,
+                                    failure_reason: None,
+                                    retries_left: retries_left(&finished_job.retry_policy),
+                                    retry_policy: finished_job.retry_policy,
+                                    msg_policies: finished_job.msg_policies,
+                                    msg_results: None,
+                                    branches: finished_job.branches,
+                                    else_msgs: finished_job.else_msgs,
+                                    output_bindings: finished_job.output_bindings,
                                 }),
                                 Some(_) => Err(ContractError::JobAlreadyExists {}),
-                            },
-                        )?;
+                            })?;
 
-                        state.current_job_id = state.current_job_id.checked_add(Uint64::new(1))?;
                         state.q = state.q.checked_add(Uint64::new(1))?;
 
                         msgs.push(
-                            //send reward to controller
-                            WasmMsg::Execute {
-                                contract_addr: account.account.to_string(),
-                                msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
-                                    msgs: vec![CosmosMsg::Bank(BankMsg::Send {
-                                        to_address: config.fee_collector.to_string(),
-                                        amount: vec![Coin::new(
-                                            (fee).u128(),
-                                            config.fee_denom.clone(),
-                                        )],
-                                    })],
-                                }))?,
-                                funds: vec![],
-                            },
+                            //send fee to fee collector
+                            account_transfer_msg(
+                                &account.account,
+                                &reward_asset,
+                                fee,
+                                &config.fee_collector,
+                            )?,
                         );
 
                         msgs.push(
-                            //send reward to controller
-                            WasmMsg::Execute {
-                                contract_addr: account.account.to_string(),
-                                msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
-                                    msgs: vec![CosmosMsg::Bank(BankMsg::Send {
-                                        to_address: env.contract.address.to_string(),
-                                        amount: vec![Coin::new(
-                                            (new_job.reward).u128(),
-                                            config.fee_denom,
-                                        )],
-                                    })],
-                                }))?,
-                                funds: vec![],
-                            },
+                            //send reward and gas budget to controller
+                            account_transfer_msg(
+                                &account.account,
+                                &reward_asset,
+                                new_job.reward.checked_add(gas_budget(&new_job))?,
+                                &env.contract.address,
+                            )?,
                         );
 
                         msgs.push(
                             //withdraw all assets that are listed
-                            WasmMsg::Execute {
+                            CosmosMsg::Wasm(WasmMsg::Execute {
                                 contract_addr: account.account.to_string(),
                                 msg: to_binary(&account::ExecuteMsg::WithdrawAssets(
                                     WithdrawAssetsMsg {
@@ -501,7 +785,7 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                                     },
                                 ))?,
                                 funds: vec![],
-                            },
+                            }),
                         );
 
                         new_job_attrs.push(Attribute::new("action", "create_job"));
@@ -537,6 +821,7 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                 .add_attribute("action", "execute_reply")
                 .add_attribute("job_id", job.id)
                 .add_attributes(res_attrs)
+                .add_attributes(gas_attrs)
                 .add_attributes(new_job_attrs)
                 .add_messages(msgs))
         }
@@ -1,4 +1,5 @@
 use crate::ContractError::{CustomError, DecodeError, DeserializationError, SerializationError};
+use controller::job::JobFailureReason;
 use cosmwasm_std::{DivideByZeroError, OverflowError, StdError};
 use std::num::ParseIntError;
 use std::str::ParseBoolError;
@@ -81,6 +82,93 @@ pub enum ContractError {
 
     #[error("Eviction period not elapsed.")]
     EvictionPeriodNotElapsed {},
+
+    #[error("Invalid schedule: {msg:?}")]
+    InvalidSchedule { msg: String },
+
+    #[error("Job not yet eligible for execution.")]
+    JobNotEligible {},
+
+    #[error("Max executions must be greater than zero.")]
+    MaxExecutionsTooSmall {},
+
+    #[error("Stable ids are only available to recurring jobs.")]
+    StableIdNotRecurring {},
+
+    #[error("Job expiration must be in the future.")]
+    ExpirationInPast {},
+
+    #[error("Reward asset must be a native denom or a cw20 token.")]
+    InvalidRewardAsset {},
+
+    #[error("Reward asset is not allowed.")]
+    RewardAssetNotAllowed {},
+
+    #[error("Invalid dependencies: {msg:?}")]
+    InvalidDependencies { msg: String },
+
+    #[error("Job dependencies must not form a cycle.")]
+    DependencyCycle {},
+
+    #[error("Job dependencies are not met.")]
+    DependenciesNotMet {},
+
+    #[error("Gas limit must be greater than zero and within the configured maximum.")]
+    InvalidGasLimit {},
+
+    #[error("Keeper slash percentage cannot be higher than 100.")]
+    SlashPercentageTooHigh {},
+
+    #[error("Keeper is not registered.")]
+    KeeperNotRegistered {},
+
+    #[error("Keeper stake is below the required minimum.")]
+    InsufficientKeeperStake {},
+
+    #[error("Keeper stake must be bonded in the fee denom.")]
+    InvalidKeeperStake {},
+
+    #[error("Keeper unbond amount must be above zero.")]
+    InvalidKeeperUnbondAmount {},
+
+    #[error("Keeper has no unbonded stake ready to withdraw.")]
+    KeeperUnbondingNotReleased {},
+
+    #[error("Exclusivity needs between 1 and 10 keepers and a non-zero window.")]
+    InvalidExclusivity {},
+
+    #[error("Job can only be executed by its exclusive keepers for now.")]
+    ExclusiveExecutionWindow {},
+
+    #[error("Reward curve must start above zero, at most at the job reward, and ramp over a non-zero period.")]
+    InvalidRewardCurve {},
+
+    #[error("Gas allowance needs a gas limit, a non-zero budget and a non-zero gas price.")]
+    InvalidGasAllowance {},
+
+    #[error("Job is paused.")]
+    JobPaused {},
+
+    #[error("Pruning of finished jobs is not enabled.")]
+    PruningDisabled {},
+
+    #[error("Batch must contain between 1 and {max:?} jobs.")]
+    InvalidBatchSize { max: usize },
+
+    #[error("Retry policy needs between 1 and {max:?} retries.")]
+    InvalidRetryPolicy { max: u64 },
+
+    #[error("Invalid branches: {msg:?}")]
+    InvalidBranches { msg: String },
+
+    #[error("Invalid output bindings: {msg:?}")]
+    InvalidOutputBindings { msg: String },
+
+    #[error("Msg policies need an account on the current account code, migrate it first.")]
+    AccountOutdated {},
+
+    #[error("Cannot both set and clear {field:?}.")]
+    ConflictingConfigUpdate { field: String },
 }
 
 impl From<serde_json_wasm::de::Error> for ContractError {
@@ -145,6 +233,45 @@ impl From<OverflowError> for ContractError {
     }
 }
 
+pub fn is_out_of_gas_error(e: &str) -> bool {
+    e.contains("out of gas")
+        || (e.contains("sdk") && e.contains("code: 11"))
+        || (e.contains("wasm") && e.contains("code: 6"))
+}
+
+// typed reason for a failed submessage, errors reach the reply as "codespace: wasm, code: 5"
+pub fn job_failure_reason(e: &str) -> JobFailureReason {
+    if is_out_of_gas_error(e) {
+        return JobFailureReason::OutOfGas;
+    }
+
+    let module = e.find("codespace: ").map(|start| {
+        e[start + "codespace: ".len()..]
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    });
+    let code = e.find("code: ").and_then(|start| {
+        e[start + "code: ".len()..]
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .and_then(|code| code.parse::<u32>().ok())
+    });
+
+    if e.contains("insufficient funds") || (module.as_deref() == Some("sdk") && code == Some(5)) {
+        return JobFailureReason::InsufficientFunds {
+            msg: map_contract_error(e),
+        };
+    }
+
+    JobFailureReason::SubmsgError {
+        module,
+        code,
+        msg: map_contract_error(e),
+    }
+}
+
 pub fn map_contract_error(e: &str) -> String {
     if e.contains("wasm") {
         if e.contains("code: 28") {
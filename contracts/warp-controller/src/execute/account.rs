@@ -28,12 +28,7 @@ pub fn create_account(
     if ACCOUNTS().has(deps.storage, info.sender.clone()) {
         let account = ACCOUNTS().load(deps.storage, info.sender.clone())?;
 
-        let cw_funds_vec = match data.funds {
-            None => {
-                vec![]
-            }
-            Some(funds) => funds,
-        };
+        let cw_funds_vec = data.funds.unwrap_or_default();
 
         let mut msgs_vec: Vec<CosmosMsg> = vec![];
 
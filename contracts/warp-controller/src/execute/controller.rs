@@ -1,4 +1,7 @@
-use crate::state::{ACCOUNTS, CONFIG, FINISHED_JOBS, PENDING_JOBS};
+use crate::state::{
+    ACCOUNTS, CONFIG, FINISHED_JOBS, JOB_INDEX_MIGRATION, PAUSED_JOBS, PENDING_JOBS,
+};
+use crate::util::asset::validate_reward_assets;
 use crate::ContractError;
 use controller::{MigrateAccountsMsg, MigrateJobsMsg, UpdateConfigMsg};
 use cosmwasm_schema::cw_serde;
@@ -115,6 +118,47 @@ pub fn update_config(
     config.t_min = data.t_min.unwrap_or(config.t_min);
     config.q_max = data.q_max.unwrap_or(config.q_max);
 
+    if let Some(reward_assets) = data.reward_assets {
+        config.reward_assets = validate_reward_assets(deps.api, reward_assets)?;
+    }
+
+    match (data.max_job_gas_limit, data.clear_max_job_gas_limit) {
+        (Some(_), Some(true)) => {
+            return Err(ContractError::ConflictingConfigUpdate {
+                field: "max_job_gas_limit".to_string(),
+            })
+        }
+        (_, Some(true)) => config.max_job_gas_limit = None,
+        (Some(max_job_gas_limit), _) => config.max_job_gas_limit = Some(max_job_gas_limit),
+        (None, _) => {}
+    }
+
+    config.keeper_min_stake = data.keeper_min_stake.unwrap_or(config.keeper_min_stake);
+    config.keeper_slash_percentage = data
+        .keeper_slash_percentage
+        .unwrap_or(config.keeper_slash_percentage);
+    config.keeper_unbonding_period = data
+        .keeper_unbonding_period
+        .unwrap_or(config.keeper_unbonding_period);
+
+    match (
+        data.finished_job_retention,
+        data.clear_finished_job_retention,
+    ) {
+        (Some(_), Some(true)) => {
+            return Err(ContractError::ConflictingConfigUpdate {
+                field: "finished_job_retention".to_string(),
+            })
+        }
+        (_, Some(true)) => config.finished_job_retention = None,
+        (Some(finished_job_retention), _) => {
+            config.finished_job_retention = Some(finished_job_retention)
+        }
+        (None, _) => {}
+    }
+
+    config.prune_bounty = data.prune_bounty.unwrap_or(config.prune_bounty);
+
     if config.a_max < config.a_min {
         return Err(ContractError::MaxFeeUnderMinFee {});
     }
@@ -135,6 +179,10 @@ pub fn update_config(
         return Err(ContractError::CancellationFeeTooHigh {});
     }
 
+    if config.keeper_slash_percentage.u64() > 100 {
+        return Err(ContractError::SlashPercentageTooHigh {});
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new()
@@ -154,7 +202,29 @@ pub fn update_config(
         .add_attribute("config_a_min", config.a_min)
         .add_attribute("config_t_max", config.t_max)
         .add_attribute("config_t_min", config.t_min)
-        .add_attribute("config_q_max", config.q_max))
+        .add_attribute("config_q_max", config.q_max)
+        .add_attribute(
+            "config_reward_assets",
+            serde_json_wasm::to_string(&config.reward_assets)?,
+        )
+        .add_attribute(
+            "config_max_job_gas_limit",
+            serde_json_wasm::to_string(&config.max_job_gas_limit)?,
+        )
+        .add_attribute("config_keeper_min_stake", config.keeper_min_stake)
+        .add_attribute(
+            "config_keeper_slash_percentage",
+            config.keeper_slash_percentage,
+        )
+        .add_attribute(
+            "config_keeper_unbonding_period",
+            config.keeper_unbonding_period,
+        )
+        .add_attribute(
+            "config_finished_job_retention",
+            serde_json_wasm::to_string(&config.finished_job_retention)?,
+        )
+        .add_attribute("config_prune_bounty", config.prune_bounty))
 }
 
 pub fn migrate_accounts(
@@ -288,6 +358,29 @@ pub fn migrate_pending_jobs(
                 requeue_on_evict: v1_job.requeue_on_evict,
                 reward: v1_job.reward,
                 assets_to_withdraw: v1_job.assets_to_withdraw,
+                schedule: None,
+                eligible_at: None,
+                max_executions: None,
+                expires_at: None,
+                executions: Uint64::zero(),
+                reward_asset: None,
+                dependencies: vec![],
+                gas_limit: None,
+                exclusivity: None,
+                exclusive_until: None,
+                reward_curve: None,
+                gas_allowance: None,
+                finished_at: None,
+                series_id: v1_job.id,
+                stable_id: false,
+                failure_reason: None,
+                retry_policy: None,
+                retries_left: Uint64::zero(),
+                msg_policies: None,
+                msg_results: None,
+                branches: vec![],
+                else_msgs: None,
+                output_bindings: vec![],
             },
         )?;
     }
@@ -390,9 +483,89 @@ pub fn migrate_finished_jobs(
                 requeue_on_evict: v1_job.requeue_on_evict,
                 reward: v1_job.reward,
                 assets_to_withdraw: v1_job.assets_to_withdraw,
+                schedule: None,
+                eligible_at: None,
+                max_executions: None,
+                expires_at: None,
+                executions: Uint64::zero(),
+                reward_asset: None,
+                dependencies: vec![],
+                gas_limit: None,
+                exclusivity: None,
+                exclusive_until: None,
+                reward_curve: None,
+                gas_allowance: None,
+                finished_at: None,
+                series_id: v1_job.id,
+                stable_id: false,
+                failure_reason: None,
+                retry_policy: None,
+                retries_left: Uint64::zero(),
+                msg_policies: None,
+                msg_results: None,
+                branches: vec![],
+                else_msgs: None,
+                output_bindings: vec![],
             },
         )?;
     }
 
     Ok(Response::new())
 }
+
+// rewrites jobs in place so indexes added after they were stored get their entries,
+// pages through pending, paused and finished jobs together in id order
+pub fn migrate_job_indexes(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: MigrateJobsMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let limit = msg.limit as usize;
+    let mut job_keys = vec![];
+    for map in [PENDING_JOBS(), PAUSED_JOBS(), FINISHED_JOBS()] {
+        job_keys.extend(
+            map.keys(
+                deps.storage,
+                msg.start_after.map(|id| Bound::exclusive(id.u64())),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<Result<Vec<_>, _>>()?,
+        );
+    }
+    job_keys.sort_unstable();
+    job_keys.truncate(limit);
+
+    for job_key in &job_keys {
+        for map in [PENDING_JOBS(), PAUSED_JOBS(), FINISHED_JOBS()] {
+            if let Some(job) = map.may_load(deps.storage, *job_key)? {
+                map.save(deps.storage, *job_key, &job)?;
+            }
+        }
+    }
+
+    // job counts are served again once contiguous pages have covered every job
+    if JOB_INDEX_MIGRATION.may_load(deps.storage)? == Some(msg.start_after) {
+        if job_keys.len() < limit {
+            JOB_INDEX_MIGRATION.remove(deps.storage);
+        } else {
+            JOB_INDEX_MIGRATION.save(deps.storage, &job_keys.last().map(|id| Uint64::new(*id)))?;
+        }
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "migrate_job_indexes")
+        .add_attribute("jobs_migrated", job_keys.len().to_string());
+    if let Some(last) = job_keys.last() {
+        res = res.add_attribute("last_job_id", last.to_string());
+    }
+
+    Ok(res)
+}
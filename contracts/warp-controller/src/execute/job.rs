@@ -1,28 +1,219 @@
-use crate::state::{ACCOUNTS, CONFIG, FINISHED_JOBS, PENDING_JOBS, STATE};
+use crate::state::{
+    JobDispatch, ACCOUNTS, CONFIG, FINISHED_JOBS, JOB_DISPATCHES, JOB_REVISIONS, JOB_SUMMARIES,
+    KEEPERS, PAUSED_JOBS, PENDING_JOBS, STATE,
+};
+use crate::util::asset::{
+    account_transfer_msg, assert_reward_asset_allowed, job_reward_asset, query_asset_balance,
+    transfer_msg,
+};
+use crate::util::branch::{job_branches, select_branch, validate_branches};
+use crate::util::dependency::{dependencies_met, validate_dependencies};
+use crate::util::history::{record_run, series_id};
+use crate::util::keeper::{add_keeper_reward, increment, load_executing_keeper, slash_keeper};
+use crate::util::limits::{is_expired, job_gas_limit, validate_gas_limit};
+use crate::util::output::validate_output_bindings;
+use crate::util::policy::{account_execute_msg, assert_account_supports_policies};
+use crate::util::retry::{retries_left, validate_retry_policy};
+use crate::util::reward::{
+    effective_reward, eviction_fee, gas_budget, validate_gas_allowance, validate_reward_curve,
+};
+use crate::util::schedule::{exclusivity_end, initial_eligibility, requeued_eligibility};
+use crate::util::summary::job_summary;
 use crate::ContractError;
 use crate::ContractError::EvictionPeriodNotElapsed;
 use account::GenericMsg;
+use controller::account::{Account, AssetInfo};
 use controller::job::{
-    CreateJobMsg, DeleteJobMsg, EvictJobMsg, ExecuteJobMsg, Job, JobStatus, UpdateJobMsg,
+    CreateJobMsg, CreateJobsMsg, DeleteJobMsg, EvictJobMsg, ExecuteJobMsg, ExternalInput, Job,
+    JobExclusivity, JobFailureReason, JobFieldChange, JobRevision, JobStatus, PauseJobMsg,
+    PruneFinishedJobsMsg, ResumeJobMsg, UpdateJobMsg,
 };
-use controller::State;
+use controller::{Config, State};
+use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{
-    to_binary, Attribute, BalanceResponse, BankMsg, BankQuery, Coin, CosmosMsg, DepsMut, Env,
-    MessageInfo, QueryRequest, ReplyOn, Response, StdResult, SubMsg, Uint128, Uint64, WasmMsg,
+    to_binary, Addr, Api, Attribute, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Order, ReplyOn, Response, StdResult, SubMsg, Uint128, Uint64, WasmMsg,
 };
+use cw_storage_plus::Bound;
+use cw_utils::{Duration, Expiration};
 use resolver::QueryHydrateMsgsMsg;
+use std::cmp::max;
 
 const MAX_TEXT_LENGTH: usize = 280;
+const MAX_BATCH_SIZE: usize = 50;
+const MAX_EXCLUSIVE_KEEPERS: usize = 10;
 
 pub fn create_job(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     data: CreateJobMsg,
 ) -> Result<Response, ContractError> {
     let state = STATE.load(deps.storage)?;
     let config = CONFIG.load(deps.storage)?;
+    let account = load_sender_account(&deps, info.sender)?;
+
+    let (job, reward_asset) = store_new_job(
+        deps.branch(),
+        &env,
+        &config,
+        account.owner.clone(),
+        state.current_job_id,
+        data,
+    )?;
+
+    STATE.save(
+        deps.storage,
+        &State {
+            current_job_id: state.current_job_id.checked_add(Uint64::new(1))?,
+            q: state.q.checked_add(Uint64::new(1))?,
+        },
+    )?;
+
+    //assume reward.amount == warp token allowance
+    let fee = job.reward * Uint128::from(config.creation_fee_percentage) / Uint128::new(100);
+
+    let reward_send_msgs = vec![
+        //send reward and gas budget to controller
+        account_transfer_msg(
+            &account.account,
+            &reward_asset,
+            job.reward.checked_add(gas_budget(&job))?,
+            &env.contract.address,
+        )?,
+        account_transfer_msg(&account.account, &reward_asset, fee, &config.fee_collector)?,
+    ];
+
+    Ok(Response::new()
+        .add_messages(reward_send_msgs)
+        .add_attribute("action", "create_job")
+        .add_attribute("job_id", job.id)
+        .add_attribute("job_owner", job.owner)
+        .add_attribute("job_name", job.name)
+        .add_attribute("job_status", serde_json_wasm::to_string(&job.status)?)
+        .add_attribute("job_condition", serde_json_wasm::to_string(&job.condition)?)
+        .add_attribute("job_msgs", serde_json_wasm::to_string(&job.msgs)?)
+        .add_attribute("job_reward", job.reward)
+        .add_attribute(
+            "job_reward_asset",
+            serde_json_wasm::to_string(&reward_asset)?,
+        )
+        .add_attribute("job_creation_fee", fee)
+        .add_attribute(
+            "job_dependencies",
+            serde_json_wasm::to_string(&job.dependencies)?,
+        )
+        .add_attribute("job_last_updated_time", job.last_update_time))
+}
+
+pub fn create_jobs(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: CreateJobsMsg,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let account = load_sender_account(&deps, info.sender)?;
+
+    if data.jobs.is_empty() || data.jobs.len() > MAX_BATCH_SIZE {
+        return Err(ContractError::InvalidBatchSize {
+            max: MAX_BATCH_SIZE,
+        });
+    }
+
+    let mut job_id = state.current_job_id;
+    let mut job_ids = vec![];
+    // totals per reward asset, jobs in a batch usually share one
+    let mut rewards: Vec<(AssetInfo, Uint128)> = vec![];
+    let mut fees: Vec<(AssetInfo, Uint128)> = vec![];
+
+    for job_msg in data.jobs {
+        let (job, reward_asset) = store_new_job(
+            deps.branch(),
+            &env,
+            &config,
+            account.owner.clone(),
+            job_id,
+            job_msg,
+        )?;
+
+        let fee = job.reward * Uint128::from(config.creation_fee_percentage) / Uint128::new(100);
+        add_to_totals(
+            &mut rewards,
+            &reward_asset,
+            job.reward.checked_add(gas_budget(&job))?,
+        )?;
+        add_to_totals(&mut fees, &reward_asset, fee)?;
+
+        job_ids.push(job.id);
+        job_id = job_id.checked_add(Uint64::new(1))?;
+    }
+
+    STATE.save(
+        deps.storage,
+        &State {
+            current_job_id: job_id,
+            q: state.q.checked_add(Uint64::new(job_ids.len() as u64))?,
+        },
+    )?;
+
+    let mut transfer_msgs = vec![];
+    for (asset, amount) in &rewards {
+        //send reward to controller
+        transfer_msgs.push(transfer_msg(asset, *amount, &env.contract.address)?);
+    }
+    for (asset, amount) in &fees {
+        transfer_msgs.push(transfer_msg(asset, *amount, &config.fee_collector)?);
+    }
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: account.account.to_string(),
+            msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
+                msgs: transfer_msgs,
+            }))?,
+            funds: vec![],
+        })
+        .add_attribute("action", "create_jobs")
+        .add_attribute("job_owner", account.owner)
+        .add_attribute("job_ids", serde_json_wasm::to_string(&job_ids)?)
+        .add_attribute("job_rewards", serde_json_wasm::to_string(&rewards)?)
+        .add_attribute("job_creation_fees", serde_json_wasm::to_string(&fees)?))
+}
 
+fn add_to_totals(
+    totals: &mut Vec<(AssetInfo, Uint128)>,
+    asset: &AssetInfo,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    match totals.iter_mut().find(|(a, _)| a == asset) {
+        Some((_, total)) => *total = total.checked_add(amount)?,
+        None => totals.push((asset.clone(), amount)),
+    }
+    Ok(())
+}
+
+fn load_sender_account(deps: &DepsMut, sender: Addr) -> Result<Account, ContractError> {
+    let account_record = ACCOUNTS().idx.account.item(deps.storage, sender.clone())?;
+
+    match account_record {
+        None => ACCOUNTS()
+            .load(deps.storage, sender)
+            .map_err(|_e| ContractError::AccountDoesNotExist {}),
+        Some(record) => Ok(record.1),
+    }
+}
+
+// validates a new job and saves it as pending under the given id
+fn store_new_job(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    owner: Addr,
+    id: Uint64,
+    data: CreateJobMsg,
+) -> Result<(Job, AssetInfo), ContractError> {
     if data.name.len() > MAX_TEXT_LENGTH {
         return Err(ContractError::NameTooLong {});
     }
@@ -36,31 +227,71 @@ pub fn create_job(
     }
 
     let _validate_conditions_and_variables: Option<String> = deps.querier.query_wasm_smart(
-        config.resolver_address,
+        config.resolver_address.clone(),
         &resolver::QueryMsg::QueryValidateJobCreation(resolver::QueryValidateJobCreationMsg {
             condition: data.condition.clone(),
             terminate_condition: data.terminate_condition.clone(),
             vars: data.vars.clone(),
             msgs: data.msgs.clone(),
+            branches: data.branches.clone().unwrap_or_default(),
+            else_msgs: data.else_msgs.clone(),
         }),
     )?;
 
-    let account_record = ACCOUNTS()
-        .idx
-        .account
-        .item(deps.storage, info.sender.clone())?;
+    if data.max_executions == Some(Uint64::zero()) {
+        return Err(ContractError::MaxExecutionsTooSmall {});
+    }
 
-    let account = match account_record {
-        None => ACCOUNTS()
-            .load(deps.storage, info.sender)
-            .map_err(|_e| ContractError::AccountDoesNotExist {})?,
-        Some(record) => record.1,
+    if data.stable_id == Some(true) && !data.recurring {
+        return Err(ContractError::StableIdNotRecurring {});
+    }
+
+    if let Some(expires_at) = &data.expires_at {
+        if expires_at.is_expired(&env.block) {
+            return Err(ContractError::ExpirationInPast {});
+        }
+    }
+
+    let eligible_at = initial_eligibility(&data.schedule, &env.block)?;
+
+    let dependencies = data.dependencies.unwrap_or_default();
+    validate_dependencies(deps.storage, id, &dependencies)?;
+
+    validate_gas_limit(config, data.gas_limit)?;
+
+    validate_reward_curve(&data.reward_curve, data.reward)?;
+
+    validate_gas_allowance(config, &data.gas_allowance, data.gas_limit)?;
+
+    let retry_policy = data.retry_policy;
+    validate_retry_policy(&retry_policy)?;
+
+    let branches = data.branches.unwrap_or_default();
+    validate_branches(&branches)?;
+
+    let output_bindings = data.output_bindings.unwrap_or_default();
+    validate_output_bindings(&data.vars, &output_bindings)?;
+
+    if data.msg_policies.is_some() {
+        let account = ACCOUNTS().load(deps.storage, owner.clone())?;
+        assert_account_supports_policies(&deps.querier, config, &account.account)?;
+    }
+
+    let exclusivity = match data.exclusivity {
+        None => None,
+        Some(exclusivity) => Some(validate_exclusivity(deps.api, exclusivity)?),
     };
+    let exclusive_until = exclusivity_end(&exclusivity, &eligible_at, &env.block);
+
+    let reward_asset = data
+        .reward_asset
+        .unwrap_or_else(|| AssetInfo::Native(config.fee_denom.clone()));
+    assert_reward_asset_allowed(config, &reward_asset)?;
 
-    let job = PENDING_JOBS().update(deps.storage, state.current_job_id.u64(), |s| match s {
+    let job = PENDING_JOBS().update(deps.storage, id.u64(), |s| match s {
         None => Ok(Job {
-            id: state.current_job_id,
-            owner: account.owner,
+            id,
+            owner,
             last_update_time: Uint64::from(env.block.time.seconds()),
             name: data.name,
             status: JobStatus::Pending,
@@ -74,67 +305,48 @@ pub fn create_job(
             description: data.description,
             labels: data.labels,
             assets_to_withdraw: data.assets_to_withdraw.unwrap_or(vec![]),
+            schedule: data.schedule,
+            eligible_at,
+            max_executions: data.max_executions,
+            expires_at: data.expires_at,
+            executions: Uint64::zero(),
+            reward_asset: Some(reward_asset.clone()),
+            dependencies,
+            gas_limit: data.gas_limit,
+            exclusivity,
+            exclusive_until,
+            reward_curve: data.reward_curve,
+            gas_allowance: data.gas_allowance,
+            finished_at: None,
+            series_id: id,
+            stable_id: data.stable_id.unwrap_or(false),
+            failure_reason: None,
+            retries_left: retries_left(&retry_policy),
+            retry_policy,
+            msg_policies: data.msg_policies,
+            msg_results: None,
+            branches,
+            else_msgs: data.else_msgs,
+            output_bindings,
         }),
         Some(_) => Err(ContractError::JobAlreadyExists {}),
     })?;
 
-    STATE.save(
-        deps.storage,
-        &State {
-            current_job_id: state.current_job_id.checked_add(Uint64::new(1))?,
-            q: state.q.checked_add(Uint64::new(1))?,
-        },
-    )?;
-
-    //assume reward.amount == warp token allowance
-    let fee = data.reward * Uint128::from(config.creation_fee_percentage) / Uint128::new(100);
-
-    let reward_send_msgs = vec![
-        //send reward to controller
-        WasmMsg::Execute {
-            contract_addr: account.account.to_string(),
-            msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
-                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
-                    to_address: env.contract.address.to_string(),
-                    amount: vec![Coin::new((data.reward).u128(), config.fee_denom.clone())],
-                })],
-            }))?,
-            funds: vec![],
-        },
-        WasmMsg::Execute {
-            contract_addr: account.account.to_string(),
-            msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
-                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
-                    to_address: config.fee_collector.to_string(),
-                    amount: vec![Coin::new((fee).u128(), config.fee_denom)],
-                })],
-            }))?,
-            funds: vec![],
-        },
-    ];
-
-    Ok(Response::new()
-        .add_messages(reward_send_msgs)
-        .add_attribute("action", "create_job")
-        .add_attribute("job_id", job.id)
-        .add_attribute("job_owner", job.owner)
-        .add_attribute("job_name", job.name)
-        .add_attribute("job_status", serde_json_wasm::to_string(&job.status)?)
-        .add_attribute("job_condition", serde_json_wasm::to_string(&job.condition)?)
-        .add_attribute("job_msgs", serde_json_wasm::to_string(&job.msgs)?)
-        .add_attribute("job_reward", job.reward)
-        .add_attribute("job_creation_fee", fee)
-        .add_attribute("job_last_updated_time", job.last_update_time))
+    Ok((job, reward_asset))
 }
 
 pub fn delete_job(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     data: DeleteJobMsg,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let state = STATE.load(deps.storage)?;
+    if PAUSED_JOBS().has(deps.storage, data.id.u64()) {
+        return Err(ContractError::JobPaused {});
+    }
+
     let job = PENDING_JOBS().load(deps.storage, data.id.u64())?;
 
     if job.status != JobStatus::Pending {
@@ -146,6 +358,8 @@ pub fn delete_job(
     }
 
     let account = ACCOUNTS().load(deps.storage, info.sender)?;
+    let reward_asset = job_reward_asset(&config, &job);
+    let budget = gas_budget(&job);
 
     PENDING_JOBS().remove(deps.storage, data.id.u64())?;
     let _new_job = FINISHED_JOBS().update(deps.storage, data.id.u64(), |h| match h {
@@ -165,6 +379,29 @@ pub fn delete_job(
             description: job.description,
             labels: job.labels,
             assets_to_withdraw: job.assets_to_withdraw,
+            schedule: job.schedule,
+            eligible_at: job.eligible_at,
+            max_executions: job.max_executions,
+            expires_at: job.expires_at,
+            executions: job.executions,
+            reward_asset: job.reward_asset,
+            dependencies: job.dependencies,
+            gas_limit: job.gas_limit,
+            exclusivity: job.exclusivity,
+            exclusive_until: job.exclusive_until,
+            reward_curve: job.reward_curve,
+            gas_allowance: job.gas_allowance,
+            finished_at: Some(Uint64::new(env.block.time.seconds())),
+            series_id: job.series_id,
+            stable_id: job.stable_id,
+            failure_reason: job.failure_reason,
+            retry_policy: job.retry_policy,
+            retries_left: job.retries_left,
+            msg_policies: job.msg_policies,
+            msg_results: job.msg_results,
+            branches: job.branches,
+            else_msgs: job.else_msgs,
+            output_bindings: job.output_bindings,
         }),
         Some(_job) => Err(ContractError::JobAlreadyFinished {}),
     })?;
@@ -181,17 +418,8 @@ pub fn delete_job(
 
     let cw20_send_msgs = vec![
         //send reward minus fee back to account
-        BankMsg::Send {
-            to_address: account.account.to_string(),
-            amount: vec![Coin::new(
-                (job.reward - fee).u128(),
-                config.fee_denom.clone(),
-            )],
-        },
-        BankMsg::Send {
-            to_address: config.fee_collector.to_string(),
-            amount: vec![Coin::new(fee.u128(), config.fee_denom)],
-        },
+        transfer_msg(&reward_asset, job.reward - fee + budget, &account.account)?,
+        transfer_msg(&reward_asset, fee, &config.fee_collector)?,
     ];
 
     Ok(Response::new()
@@ -208,6 +436,10 @@ pub fn update_job(
     info: MessageInfo,
     data: UpdateJobMsg,
 ) -> Result<Response, ContractError> {
+    if PAUSED_JOBS().has(deps.storage, data.id.u64()) {
+        return Err(ContractError::JobPaused {});
+    }
+
     let job = PENDING_JOBS().load(deps.storage, data.id.u64())?;
     let config = CONFIG.load(deps.storage)?;
 
@@ -227,6 +459,85 @@ pub fn update_job(
         return Err(ContractError::NameTooShort {});
     }
 
+    if job.stable_id && data.recurring == Some(false) {
+        return Err(ContractError::StableIdNotRecurring {});
+    }
+
+    if let Some(branches) = &data.branches {
+        validate_branches(branches)?;
+    }
+
+    if data.condition.is_some()
+        || data.terminate_condition.is_some()
+        || data.msgs.is_some()
+        || data.vars.is_some()
+        || data.branches.is_some()
+        || data.else_msgs.is_some()
+    {
+        let _validate_conditions_and_variables: Option<String> = deps.querier.query_wasm_smart(
+            config.resolver_address.clone(),
+            &resolver::QueryMsg::QueryValidateJobCreation(resolver::QueryValidateJobCreationMsg {
+                condition: data.condition.clone().unwrap_or(job.condition.clone()),
+                terminate_condition: match &data.terminate_condition {
+                    None => job.terminate_condition.clone(),
+                    Some(terminate_condition) => Some(terminate_condition.clone()),
+                },
+                vars: data.vars.clone().unwrap_or(job.vars.clone()),
+                msgs: data.msgs.clone().unwrap_or(job.msgs.clone()),
+                branches: data.branches.clone().unwrap_or(job.branches.clone()),
+                else_msgs: match &data.else_msgs {
+                    None => job.else_msgs.clone(),
+                    Some(else_msgs) => Some(else_msgs.clone()),
+                },
+            }),
+        )?;
+    }
+
+    if let Some(vars) = &data.vars {
+        validate_output_bindings(vars, &job.output_bindings)?;
+    }
+
+    let mut changes = vec![];
+    record_change(&mut changes, "name", &job.name, &data.name)?;
+    record_change(
+        &mut changes,
+        "description",
+        &job.description,
+        &data.description,
+    )?;
+    record_change(&mut changes, "labels", &job.labels, &data.labels)?;
+    record_change(&mut changes, "condition", &job.condition, &data.condition)?;
+    record_change(
+        &mut changes,
+        "terminate_condition",
+        &job.terminate_condition,
+        &data.terminate_condition.clone().map(Some),
+    )?;
+    record_change(&mut changes, "msgs", &job.msgs, &data.msgs)?;
+    record_change(&mut changes, "vars", &job.vars, &data.vars)?;
+    record_change(&mut changes, "branches", &job.branches, &data.branches)?;
+    record_change(
+        &mut changes,
+        "else_msgs",
+        &job.else_msgs,
+        &data.else_msgs.clone().map(Some),
+    )?;
+    record_change(&mut changes, "recurring", &job.recurring, &data.recurring)?;
+    record_change(
+        &mut changes,
+        "requeue_on_evict",
+        &job.requeue_on_evict,
+        &data.requeue_on_evict,
+    )?;
+    if !added_reward.is_zero() {
+        record_change(
+            &mut changes,
+            "reward",
+            &job.reward,
+            &Some(job.reward.checked_add(added_reward)?),
+        )?;
+    }
+
     let job = PENDING_JOBS().update(deps.storage, data.id.u64(), |h| match h {
         None => Err(ContractError::JobDoesNotExist {}),
         Some(job) => Ok(Job {
@@ -241,14 +552,43 @@ pub fn update_job(
             description: data.description.unwrap_or(job.description),
             labels: data.labels.unwrap_or(job.labels),
             status: job.status,
-            condition: job.condition,
-            terminate_condition: job.terminate_condition,
-            msgs: job.msgs,
-            vars: job.vars,
-            recurring: job.recurring,
-            requeue_on_evict: job.requeue_on_evict,
+            condition: data.condition.unwrap_or(job.condition),
+            terminate_condition: match data.terminate_condition {
+                None => job.terminate_condition,
+                Some(terminate_condition) => Some(terminate_condition),
+            },
+            msgs: data.msgs.unwrap_or(job.msgs),
+            vars: data.vars.unwrap_or(job.vars),
+            recurring: data.recurring.unwrap_or(job.recurring),
+            requeue_on_evict: data.requeue_on_evict.unwrap_or(job.requeue_on_evict),
             reward: job.reward + added_reward,
             assets_to_withdraw: job.assets_to_withdraw,
+            schedule: job.schedule,
+            eligible_at: job.eligible_at,
+            max_executions: job.max_executions,
+            expires_at: job.expires_at,
+            executions: job.executions,
+            reward_asset: job.reward_asset,
+            dependencies: job.dependencies,
+            gas_limit: job.gas_limit,
+            exclusivity: job.exclusivity,
+            exclusive_until: job.exclusive_until,
+            reward_curve: job.reward_curve,
+            gas_allowance: job.gas_allowance,
+            finished_at: job.finished_at,
+            series_id: job.series_id,
+            stable_id: job.stable_id,
+            failure_reason: job.failure_reason,
+            retry_policy: job.retry_policy,
+            retries_left: job.retries_left,
+            msg_policies: job.msg_policies,
+            msg_results: job.msg_results,
+            branches: data.branches.unwrap_or(job.branches),
+            else_msgs: match data.else_msgs {
+                None => job.else_msgs,
+                Some(else_msgs) => Some(else_msgs),
+            },
+            output_bindings: job.output_bindings,
         }),
     })?;
 
@@ -258,35 +598,46 @@ pub fn update_job(
         return Err(ContractError::RewardTooSmall {});
     }
 
+    let mut revision = match JOB_REVISIONS
+        .prefix(job.id.u64())
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+    {
+        None => 0,
+        Some(last) => last?,
+    };
+
+    if !changes.is_empty() {
+        revision += 1;
+        JOB_REVISIONS.save(
+            deps.storage,
+            (job.id.u64(), revision),
+            &JobRevision {
+                revision: Uint64::new(revision),
+                updated_at: Uint64::new(env.block.time.seconds()),
+                changes,
+            },
+        )?;
+    }
+
     let mut cw20_send_msgs = vec![];
 
     if added_reward.u128() > 0 {
-        cw20_send_msgs.push(
-            //send reward to controller
-            WasmMsg::Execute {
-                contract_addr: account.account.to_string(),
-                msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
-                    msgs: vec![CosmosMsg::Bank(BankMsg::Send {
-                        to_address: env.contract.address.to_string(),
-                        amount: vec![Coin::new((added_reward).u128(), config.fee_denom.clone())],
-                    })],
-                }))?,
-                funds: vec![],
-            },
-        );
-        cw20_send_msgs.push(
-            //send reward to controller
-            WasmMsg::Execute {
-                contract_addr: account.account.to_string(),
-                msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
-                    msgs: vec![CosmosMsg::Bank(BankMsg::Send {
-                        to_address: config.fee_collector.to_string(),
-                        amount: vec![Coin::new((fee).u128(), config.fee_denom)],
-                    })],
-                }))?,
-                funds: vec![],
-            },
-        );
+        let reward_asset = job_reward_asset(&config, &job);
+
+        //send reward to controller
+        cw20_send_msgs.push(account_transfer_msg(
+            &account.account,
+            &reward_asset,
+            added_reward,
+            &env.contract.address,
+        )?);
+        cw20_send_msgs.push(account_transfer_msg(
+            &account.account,
+            &reward_asset,
+            fee,
+            &config.fee_collector,
+        )?);
     }
 
     Ok(Response::new()
@@ -300,18 +651,71 @@ pub fn update_job(
         .add_attribute("job_msgs", serde_json_wasm::to_string(&job.msgs)?)
         .add_attribute("job_reward", job.reward)
         .add_attribute("job_update_fee", fee)
+        .add_attribute("job_revision", revision.to_string())
         .add_attribute("job_last_updated_time", job.last_update_time))
 }
 
+fn validate_exclusivity(
+    api: &dyn Api,
+    exclusivity: JobExclusivity,
+) -> Result<JobExclusivity, ContractError> {
+    if exclusivity.keepers.is_empty() || exclusivity.keepers.len() > MAX_EXCLUSIVE_KEEPERS {
+        return Err(ContractError::InvalidExclusivity {});
+    }
+
+    if let Duration::Height(0) | Duration::Time(0) = exclusivity.window {
+        return Err(ContractError::InvalidExclusivity {});
+    }
+
+    let mut keepers = vec![];
+    for keeper in exclusivity.keepers {
+        keepers.push(api.addr_validate(keeper.as_str())?);
+    }
+
+    Ok(JobExclusivity {
+        keepers,
+        window: exclusivity.window,
+    })
+}
+
+fn keeper_slash_msg(config: &Config, amount: Uint128) -> BankMsg {
+    BankMsg::Send {
+        to_address: config.fee_collector.to_string(),
+        amount: vec![Coin::new(amount.u128(), config.fee_denom.clone())],
+    }
+}
+
+fn record_change<T: Serialize + PartialEq>(
+    changes: &mut Vec<JobFieldChange>,
+    field: &str,
+    old_value: &T,
+    new_value: &Option<T>,
+) -> Result<(), ContractError> {
+    if let Some(new_value) = new_value {
+        if new_value != old_value {
+            changes.push(JobFieldChange {
+                field: field.to_string(),
+                old_value: serde_json_wasm::to_string(old_value)?,
+                new_value: serde_json_wasm::to_string(new_value)?,
+            });
+        }
+    }
+    Ok(())
+}
+
 pub fn execute_job(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     data: ExecuteJobMsg,
 ) -> Result<Response, ContractError> {
     let _config = CONFIG.load(deps.storage)?;
     let state = STATE.load(deps.storage)?;
     let config = CONFIG.load(deps.storage)?;
+    if PAUSED_JOBS().has(deps.storage, data.id.u64()) {
+        return Err(ContractError::JobPaused {});
+    }
+
     let job = PENDING_JOBS().load(deps.storage, data.id.u64())?;
     let account = ACCOUNTS().load(deps.storage, job.owner.clone())?;
 
@@ -320,38 +724,147 @@ pub fn execute_job(
     }
 
     let keeper_account = ACCOUNTS().load(deps.storage, info.sender.clone())?;
+    let reward_asset = job_reward_asset(&config, &job);
+    let mut keeper = load_executing_keeper(deps.storage, &config, &info.sender)?;
 
     if job.status != JobStatus::Pending {
         return Err(ContractError::JobNotActive {});
     }
 
-    let vars: String = deps.querier.query_wasm_smart(
-        config.resolver_address.clone(),
-        &resolver::QueryMsg::QueryHydrateVars(resolver::QueryHydrateVarsMsg {
-            vars: job.vars,
-            external_inputs: data.external_inputs,
-        }),
-    )?;
+    if is_expired(&job, &env.block) {
+        PENDING_JOBS().remove(deps.storage, data.id.u64())?;
+        let expired_job = FINISHED_JOBS().update(deps.storage, data.id.u64(), |j| match j {
+            None => Ok(Job {
+                id: job.id,
+                owner: job.owner,
+                last_update_time: Uint64::new(env.block.time.seconds()),
+                name: job.name,
+                description: job.description,
+                labels: job.labels,
+                status: JobStatus::Expired,
+                condition: job.condition,
+                terminate_condition: job.terminate_condition,
+                msgs: job.msgs,
+                vars: job.vars,
+                recurring: job.recurring,
+                requeue_on_evict: job.requeue_on_evict,
+                reward: job.reward,
+                assets_to_withdraw: job.assets_to_withdraw,
+                schedule: job.schedule,
+                eligible_at: job.eligible_at,
+                max_executions: job.max_executions,
+                expires_at: job.expires_at,
+                executions: job.executions,
+                reward_asset: job.reward_asset,
+                dependencies: job.dependencies,
+                gas_limit: job.gas_limit,
+                exclusivity: job.exclusivity,
+                exclusive_until: job.exclusive_until,
+                reward_curve: job.reward_curve,
+                gas_allowance: job.gas_allowance,
+                finished_at: Some(Uint64::new(env.block.time.seconds())),
+                series_id: job.series_id,
+                stable_id: job.stable_id,
+                failure_reason: job.failure_reason,
+                retry_policy: job.retry_policy,
+                retries_left: job.retries_left,
+                msg_policies: job.msg_policies,
+                msg_results: job.msg_results,
+                branches: job.branches,
+                else_msgs: job.else_msgs,
+                output_bindings: job.output_bindings,
+            }),
+            Some(_) => Err(ContractError::JobAlreadyFinished {}),
+        })?;
 
-    let resolution: StdResult<bool> = deps.querier.query_wasm_smart(
-        config.resolver_address.clone(),
-        &resolver::QueryMsg::QueryResolveCondition(resolver::QueryResolveConditionMsg {
-            condition: job.condition,
-            vars: vars.clone(),
-        }),
-    );
+        STATE.save(
+            deps.storage,
+            &State {
+                current_job_id: state.current_job_id,
+                q: state.q.checked_sub(Uint64::new(1))?,
+            },
+        )?;
+
+        //refund escrowed reward to account
+        let refund = expired_job.reward.checked_add(gas_budget(&expired_job))?;
+        let refund_msg = transfer_msg(&reward_asset, refund, &account.account)?;
+
+        return Ok(Response::new()
+            .add_message(refund_msg)
+            .add_attribute("action", "execute_job")
+            .add_attribute("executor", info.sender)
+            .add_attribute("job_id", expired_job.id)
+            .add_attribute(
+                "job_status",
+                serde_json_wasm::to_string(&expired_job.status)?,
+            )
+            .add_attribute("job_refund", refund));
+    }
+
+    if let Some(eligible_at) = &job.eligible_at {
+        if !eligible_at.is_expired(&env.block) {
+            return Err(ContractError::JobNotEligible {});
+        }
+    }
+
+    if !dependencies_met(deps.storage, &job)? {
+        return Err(ContractError::DependenciesNotMet {});
+    }
+
+    if let (Some(exclusivity), Some(exclusive_until)) = (&job.exclusivity, &job.exclusive_until) {
+        if !exclusive_until.is_expired(&env.block) && !exclusivity.keepers.contains(&info.sender) {
+            return Err(ContractError::ExclusiveExecutionWindow {});
+        }
+    }
+
+    let gas_limit = job_gas_limit(&config, &job);
+    let mut reward = effective_reward(&job, env.block.time.seconds());
+    let budget = gas_budget(&job);
+    // keepers are only penalized where registration is required of every caller
+    let slashing = !config.keeper_slash_percentage.is_zero();
+
+    // hydration and resolution errors fail the job, a false condition leaves it pending
+    let (vars, outcome) = prepare_job_msgs(deps.as_ref(), &config, &job, data.external_inputs);
 
     let mut attrs = vec![];
     let mut submsgs = vec![];
+    let mut slash_msgs = vec![];
 
-    if let Err(e) = resolution {
-        attrs.push(Attribute::new("job_condition_status", "invalid"));
-        attrs.push(Attribute::new("error", e.to_string()));
-        let job = PENDING_JOBS().load(deps.storage, data.id.u64())?;
-        FINISHED_JOBS().save(
-            deps.storage,
-            data.id.u64(),
-            &Job {
+    let ready_msgs = match outcome {
+        Err(reason) => {
+            let error = match &reason {
+                JobFailureReason::ConditionInvalid { msg }
+                | JobFailureReason::HydrationError { msg } => msg.clone(),
+                _ => serde_json_wasm::to_string(&reason)?,
+            };
+            if let JobFailureReason::ConditionInvalid { .. } = reason {
+                attrs.push(Attribute::new("job_condition_status", "invalid"));
+            }
+            attrs.push(Attribute::new("error", error.clone()));
+            attrs.push(Attribute::new(
+                "job_failure_reason",
+                serde_json_wasm::to_string(&reason)?,
+            ));
+            // no submessage is dispatched, so the gas budget is not needed
+            if !budget.is_zero() {
+                slash_msgs.push(transfer_msg(&reward_asset, budget, &account.account)?);
+            }
+            if let Some(keeper) = &mut keeper {
+                increment(&mut keeper.failed)?;
+                // an invalid condition is the keeper's call, a hydration error is not. A slashed
+                // keeper earns nothing, the whole reward goes back to the owner
+                if slashing && matches!(reason, JobFailureReason::ConditionInvalid { .. }) {
+                    let slashed = slash_keeper(&config, keeper)?;
+                    if !slashed.is_zero() {
+                        slash_msgs.push(keeper_slash_msg(&config, slashed).into());
+                    }
+                    attrs.push(Attribute::new("keeper_slashed", slashed));
+                    reward = Uint128::zero();
+                }
+            }
+            let job = PENDING_JOBS().load(deps.storage, data.id.u64())?;
+            let series_id = series_id(&job);
+            let finished_job = Job {
                 id: job.id,
                 owner: job.owner,
                 last_update_time: job.last_update_time,
@@ -367,57 +880,181 @@ pub fn execute_job(
                 requeue_on_evict: job.requeue_on_evict,
                 reward: job.reward,
                 assets_to_withdraw: job.assets_to_withdraw,
-            },
-        )?;
-        PENDING_JOBS().remove(deps.storage, data.id.u64())?;
-        STATE.save(
+                schedule: job.schedule,
+                eligible_at: job.eligible_at,
+                max_executions: job.max_executions,
+                expires_at: job.expires_at,
+                executions: job.executions,
+                reward_asset: job.reward_asset,
+                dependencies: job.dependencies,
+                gas_limit: job.gas_limit,
+                exclusivity: job.exclusivity,
+                exclusive_until: job.exclusive_until,
+                reward_curve: job.reward_curve,
+                gas_allowance: job.gas_allowance,
+                finished_at: Some(Uint64::new(env.block.time.seconds())),
+                series_id,
+                stable_id: job.stable_id,
+                failure_reason: Some(reason),
+                retry_policy: job.retry_policy,
+                retries_left: job.retries_left,
+                msg_policies: job.msg_policies,
+                msg_results: job.msg_results,
+                branches: job.branches,
+                else_msgs: job.else_msgs,
+                output_bindings: job.output_bindings,
+            };
+            FINISHED_JOBS().save(deps.storage, data.id.u64(), &finished_job)?;
+            record_run(
+                deps.storage,
+                &finished_job,
+                JobStatus::Failed,
+                info.sender.clone(),
+                &env.block,
+                reward,
+                Some(error),
+            )?;
+            PENDING_JOBS().remove(deps.storage, data.id.u64())?;
+            STATE.save(
+                deps.storage,
+                &State {
+                    current_job_id: state.current_job_id,
+                    q: state.q.checked_sub(Uint64::new(1))?,
+                },
+            )?;
+            None
+        }
+        Ok(None) => {
+            // keepers pay for triggering jobs that are not ready yet, without slashing the call
+            // is rejected for registered and unregistered callers alike
+            let mut keeper = match keeper {
+                Some(keeper) if slashing => keeper,
+                _ => return Err(ContractError::JobNotActive {}),
+            };
+
+            let slashed = slash_keeper(&config, &mut keeper)?;
+            KEEPERS().save(deps.storage, info.sender.clone(), &keeper)?;
+
+            let mut res = Response::new();
+            if !slashed.is_zero() {
+                res = res.add_message(keeper_slash_msg(&config, slashed));
+            }
+
+            return Ok(res
+                .add_attribute("action", "execute_job")
+                .add_attribute("executor", info.sender)
+                .add_attribute("job_id", job.id)
+                .add_attribute("job_condition_status", "false")
+                .add_attribute("keeper_slashed", slashed));
+        }
+        Ok(Some(msgs)) => {
+            attrs.push(Attribute::new("job_condition_status", "valid"));
+            Some(msgs)
+        }
+    };
+
+    if let Some(msgs) = ready_msgs {
+        JOB_DISPATCHES.save(
             deps.storage,
-            &State {
-                current_job_id: state.current_job_id,
-                q: state.q.checked_sub(Uint64::new(1))?,
+            job.id.u64(),
+            &JobDispatch {
+                keeper: info.sender.clone(),
+                reward,
             },
         )?;
-    } else {
-        attrs.push(Attribute::new("job_condition_status", "valid"));
-        if !resolution? {
-            return Err(ContractError::JobNotActive {});
-        }
 
         submsgs.push(SubMsg {
             id: job.id.u64(),
             msg: CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: account.account.to_string(),
-                msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
-                    msgs: deps.querier.query_wasm_smart(
-                        config.resolver_address,
-                        &resolver::QueryMsg::QueryHydrateMsgs(QueryHydrateMsgsMsg {
-                            msgs: job.msgs,
-                            vars,
-                        }),
-                    )?,
-                }))?,
+                msg: to_binary(&account_execute_msg(&job, msgs))?,
                 funds: vec![],
             }),
-            gas_limit: None,
+            gas_limit,
             reply_on: ReplyOn::Always,
         });
     }
 
-    let reward_msg = BankMsg::Send {
-        to_address: keeper_account.account.to_string(),
-        amount: vec![Coin::new(job.reward.u128(), config.fee_denom)],
-    };
+    let mut reward_msgs = vec![];
+    if !reward.is_zero() {
+        reward_msgs.push(transfer_msg(
+            &reward_asset,
+            reward,
+            &keeper_account.account,
+        )?);
+    }
+
+    // escrow not paid out on the reward curve goes back to the owner
+    let refund = job.reward.checked_sub(reward)?;
+    if !refund.is_zero() {
+        reward_msgs.push(transfer_msg(&reward_asset, refund, &account.account)?);
+    }
+
+    if let Some(keeper) = &mut keeper {
+        if !reward.is_zero() {
+            add_keeper_reward(keeper, &reward_asset, reward)?;
+        }
+        KEEPERS().save(deps.storage, info.sender.clone(), keeper)?;
+    }
 
     Ok(Response::new()
         .add_submessages(submsgs)
-        .add_message(reward_msg)
+        .add_messages(reward_msgs)
+        .add_messages(slash_msgs)
         .add_attribute("action", "execute_job")
         .add_attribute("executor", info.sender)
         .add_attribute("job_id", job.id)
-        .add_attribute("job_reward", job.reward)
+        .add_attribute("job_reward", reward)
+        .add_attribute("job_reward_refund", refund)
         .add_attributes(attrs))
 }
 
+// hydrated vars of a job and its hydrated msgs, None while the condition does not hold
+fn prepare_job_msgs(
+    deps: Deps,
+    config: &Config,
+    job: &Job,
+    external_inputs: Option<Vec<ExternalInput>>,
+) -> (String, Result<Option<Vec<CosmosMsg>>, JobFailureReason>) {
+    let vars: StdResult<String> = deps.querier.query_wasm_smart(
+        config.resolver_address.clone(),
+        &resolver::QueryMsg::QueryHydrateVars(resolver::QueryHydrateVarsMsg {
+            vars: job.vars.clone(),
+            external_inputs,
+        }),
+    );
+    let vars = match vars {
+        Ok(vars) => vars,
+        Err(e) => {
+            return (
+                job.vars.clone(),
+                Err(JobFailureReason::HydrationError { msg: e.to_string() }),
+            )
+        }
+    };
+
+    let resolution = select_branch(deps, config, &job_branches(job), &job.else_msgs, &vars);
+
+    // only the msgs of the selected branch are hydrated
+    let outcome = match resolution {
+        Err(e) => Err(JobFailureReason::ConditionInvalid { msg: e.to_string() }),
+        Ok(None) => Ok(None),
+        Ok(Some(branch_msgs)) => {
+            let msgs: StdResult<Vec<CosmosMsg>> = deps.querier.query_wasm_smart(
+                config.resolver_address.clone(),
+                &resolver::QueryMsg::QueryHydrateMsgs(QueryHydrateMsgsMsg {
+                    msgs: branch_msgs,
+                    vars: vars.clone(),
+                }),
+            );
+            msgs.map(Some)
+                .map_err(|e| JobFailureReason::HydrationError { msg: e.to_string() })
+        }
+    };
+
+    (vars, outcome)
+}
+
 pub fn evict_job(
     deps: DepsMut,
     env: Env,
@@ -426,17 +1063,16 @@ pub fn evict_job(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let state = STATE.load(deps.storage)?;
+    if PAUSED_JOBS().has(deps.storage, data.id.u64()) {
+        return Err(ContractError::JobPaused {});
+    }
+
     let job = PENDING_JOBS().load(deps.storage, data.id.u64())?;
     let account = ACCOUNTS().load(deps.storage, job.owner.clone())?;
 
-    let account_amount = deps
-        .querier
-        .query::<BalanceResponse>(&QueryRequest::Bank(BankQuery::Balance {
-            address: account.account.to_string(),
-            denom: config.fee_denom.clone(),
-        }))?
-        .amount
-        .amount;
+    let reward_asset = job_reward_asset(&config, &job);
+
+    let account_amount = query_asset_balance(&deps.querier, &reward_asset, &account.account)?;
 
     if job.status != JobStatus::Pending {
         return Err(ContractError::Unauthorized {});
@@ -448,33 +1084,50 @@ pub fn evict_job(
         config.t_min
     };
 
-    let a = if state.q < config.q_max {
-        config.a_min
-    } else {
-        config.a_max
+    let a = eviction_fee(
+        &config,
+        &job,
+        if state.q < config.q_max {
+            config.a_min
+        } else {
+            config.a_max
+        },
+    );
+
+    // scheduled jobs only start aging once they become eligible, a job eligible from a height
+    // ages from its last update once that height is reached
+    let eviction_clock_start = match job.eligible_at {
+        Some(Expiration::AtTime(eligible_at)) => {
+            max(job.last_update_time.u64(), eligible_at.seconds())
+        }
+        Some(Expiration::AtHeight(eligible_at)) if env.block.height < eligible_at => {
+            return Err(EvictionPeriodNotElapsed {});
+        }
+        _ => job.last_update_time.u64(),
     };
 
-    if env.block.time.seconds() - job.last_update_time.u64() < t.u64() {
+    if env
+        .block
+        .time
+        .seconds()
+        .saturating_sub(eviction_clock_start)
+        < t.u64()
+    {
         return Err(EvictionPeriodNotElapsed {});
     }
 
     let mut cosmos_msgs = vec![];
 
     let job_status;
+    let expired = is_expired(&job, &env.block);
+    let budget = gas_budget(&job);
 
-    if job.requeue_on_evict && account_amount >= a {
+    if job.requeue_on_evict && !expired && account_amount >= a {
+        let eligible_at = requeued_eligibility(&job.schedule, &job.eligible_at, &env.block)?;
+        let exclusive_until = exclusivity_end(&job.exclusivity, &eligible_at, &env.block);
         cosmos_msgs.push(
             //send reward to evictor
-            CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: account.account.to_string(),
-                msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
-                    msgs: vec![CosmosMsg::Bank(BankMsg::Send {
-                        to_address: info.sender.to_string(),
-                        amount: vec![Coin::new(a.u128(), config.fee_denom)],
-                    })],
-                }))?,
-                funds: vec![],
-            }),
+            account_transfer_msg(&account.account, &reward_asset, a, &info.sender)?,
         );
         job_status = PENDING_JOBS()
             .update(deps.storage, data.id.u64(), |j| match j {
@@ -495,10 +1148,38 @@ pub fn evict_job(
                     requeue_on_evict: job.requeue_on_evict,
                     reward: job.reward,
                     assets_to_withdraw: job.assets_to_withdraw,
+                    schedule: job.schedule,
+                    eligible_at,
+                    max_executions: job.max_executions,
+                    expires_at: job.expires_at,
+                    executions: job.executions,
+                    reward_asset: job.reward_asset,
+                    dependencies: job.dependencies,
+                    gas_limit: job.gas_limit,
+                    exclusivity: job.exclusivity,
+                    exclusive_until,
+                    reward_curve: job.reward_curve,
+                    gas_allowance: job.gas_allowance,
+                    finished_at: job.finished_at,
+                    series_id: job.series_id,
+                    stable_id: job.stable_id,
+                    failure_reason: job.failure_reason,
+                    retry_policy: job.retry_policy,
+                    retries_left: job.retries_left,
+                    msg_policies: job.msg_policies,
+                    msg_results: job.msg_results,
+                    branches: job.branches,
+                    else_msgs: job.else_msgs,
+                    output_bindings: job.output_bindings,
                 }),
             })?
             .status;
     } else {
+        let refund = job
+            .reward
+            .checked_sub(a)
+            .map_err(|_| ContractError::RewardSmallerThanFee {})?
+            + budget;
         PENDING_JOBS().remove(deps.storage, data.id.u64())?;
         job_status = FINISHED_JOBS()
             .update(deps.storage, data.id.u64(), |j| match j {
@@ -509,7 +1190,11 @@ pub fn evict_job(
                     name: job.name,
                     description: job.description,
                     labels: job.labels,
-                    status: JobStatus::Evicted,
+                    status: if expired {
+                        JobStatus::Expired
+                    } else {
+                        JobStatus::Evicted
+                    },
                     condition: job.condition,
                     terminate_condition: job.terminate_condition,
                     msgs: job.msgs,
@@ -518,6 +1203,29 @@ pub fn evict_job(
                     requeue_on_evict: job.requeue_on_evict,
                     reward: job.reward,
                     assets_to_withdraw: job.assets_to_withdraw,
+                    schedule: job.schedule,
+                    eligible_at: job.eligible_at,
+                    max_executions: job.max_executions,
+                    expires_at: job.expires_at,
+                    executions: job.executions,
+                    reward_asset: job.reward_asset,
+                    dependencies: job.dependencies,
+                    gas_limit: job.gas_limit,
+                    exclusivity: job.exclusivity,
+                    exclusive_until: job.exclusive_until,
+                    reward_curve: job.reward_curve,
+                    gas_allowance: job.gas_allowance,
+                    finished_at: Some(Uint64::new(env.block.time.seconds())),
+                    series_id: job.series_id,
+                    stable_id: job.stable_id,
+                    failure_reason: job.failure_reason,
+                    retry_policy: job.retry_policy,
+                    retries_left: job.retries_left,
+                    msg_policies: job.msg_policies,
+                    msg_results: job.msg_results,
+                    branches: job.branches,
+                    else_msgs: job.else_msgs,
+                    output_bindings: job.output_bindings,
                 }),
                 Some(_) => Err(ContractError::JobAlreadyExists {}),
             })?
@@ -525,14 +1233,8 @@ pub fn evict_job(
 
         cosmos_msgs.append(&mut vec![
             //send reward minus fee back to account
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: info.sender.to_string(),
-                amount: vec![Coin::new(a.u128(), config.fee_denom.clone())],
-            }),
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: account.account.to_string(),
-                amount: vec![Coin::new((job.reward - a).u128(), config.fee_denom)],
-            }),
+            transfer_msg(&reward_asset, a, &info.sender)?,
+            transfer_msg(&reward_asset, refund, &account.account)?,
         ]);
 
         STATE.save(
@@ -544,9 +1246,273 @@ pub fn evict_job(
         )?;
     }
 
+    if let Some(mut keeper) = KEEPERS().may_load(deps.storage, info.sender.clone())? {
+        increment(&mut keeper.evicted)?;
+        add_keeper_reward(&mut keeper, &reward_asset, a)?;
+        KEEPERS().save(deps.storage, info.sender, &keeper)?;
+    }
+
     Ok(Response::new()
         .add_attribute("action", "evict_job")
         .add_attribute("job_id", job.id)
         .add_attribute("job_status", serde_json_wasm::to_string(&job_status)?)
         .add_messages(cosmos_msgs))
 }
+
+pub fn pause_job(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    data: PauseJobMsg,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let job = PENDING_JOBS().load(deps.storage, data.id.u64())?;
+
+    if job.status != JobStatus::Pending {
+        return Err(ContractError::JobNotActive {});
+    }
+
+    if job.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PENDING_JOBS().remove(deps.storage, data.id.u64())?;
+    let job = PAUSED_JOBS().update(deps.storage, data.id.u64(), |j| match j {
+        None => Ok(Job {
+            id: job.id,
+            owner: job.owner,
+            last_update_time: job.last_update_time,
+            name: job.name,
+            description: job.description,
+            labels: job.labels,
+            status: JobStatus::Paused,
+            condition: job.condition,
+            terminate_condition: job.terminate_condition,
+            msgs: job.msgs,
+            vars: job.vars,
+            recurring: job.recurring,
+            requeue_on_evict: job.requeue_on_evict,
+            reward: job.reward,
+            assets_to_withdraw: job.assets_to_withdraw,
+            schedule: job.schedule,
+            eligible_at: job.eligible_at,
+            max_executions: job.max_executions,
+            expires_at: job.expires_at,
+            executions: job.executions,
+            reward_asset: job.reward_asset,
+            dependencies: job.dependencies,
+            gas_limit: job.gas_limit,
+            exclusivity: job.exclusivity,
+            exclusive_until: job.exclusive_until,
+            reward_curve: job.reward_curve,
+            gas_allowance: job.gas_allowance,
+            finished_at: job.finished_at,
+            series_id: job.series_id,
+            stable_id: job.stable_id,
+            failure_reason: job.failure_reason,
+            retry_policy: job.retry_policy,
+            retries_left: job.retries_left,
+            msg_policies: job.msg_policies,
+            msg_results: job.msg_results,
+            branches: job.branches,
+            else_msgs: job.else_msgs,
+            output_bindings: job.output_bindings,
+        }),
+        Some(_) => Err(ContractError::JobAlreadyExists {}),
+    })?;
+
+    // paused jobs do not count towards the queue length used for eviction fees
+    STATE.save(
+        deps.storage,
+        &State {
+            current_job_id: state.current_job_id,
+            q: state.q.checked_sub(Uint64::new(1))?,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause_job")
+        .add_attribute("job_id", job.id)
+        .add_attribute("job_owner", job.owner)
+        .add_attribute("job_status", serde_json_wasm::to_string(&job.status)?))
+}
+
+pub fn resume_job(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: ResumeJobMsg,
+) -> Result<Response, ContractError> {
+    let state = STATE.load(deps.storage)?;
+    let job = PAUSED_JOBS().load(deps.storage, data.id.u64())?;
+
+    if job.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // a window that closed while the job was paused opens again
+    let eligible_at = requeued_eligibility(&job.schedule, &job.eligible_at, &env.block)?;
+    let exclusive_until = exclusivity_end(&job.exclusivity, &eligible_at, &env.block);
+
+    PAUSED_JOBS().remove(deps.storage, data.id.u64())?;
+    let job = PENDING_JOBS().update(deps.storage, data.id.u64(), |j| match j {
+        None => Ok(Job {
+            id: job.id,
+            owner: job.owner,
+            // the eviction clock restarts, the job was not executable while paused
+            last_update_time: Uint64::new(env.block.time.seconds()),
+            name: job.name,
+            description: job.description,
+            labels: job.labels,
+            status: JobStatus::Pending,
+            condition: job.condition,
+            terminate_condition: job.terminate_condition,
+            msgs: job.msgs,
+            vars: job.vars,
+            recurring: job.recurring,
+            requeue_on_evict: job.requeue_on_evict,
+            reward: job.reward,
+            assets_to_withdraw: job.assets_to_withdraw,
+            schedule: job.schedule,
+            eligible_at,
+            max_executions: job.max_executions,
+            expires_at: job.expires_at,
+            executions: job.executions,
+            reward_asset: job.reward_asset,
+            dependencies: job.dependencies,
+            gas_limit: job.gas_limit,
+            exclusivity: job.exclusivity,
+            exclusive_until,
+            reward_curve: job.reward_curve,
+            gas_allowance: job.gas_allowance,
+            finished_at: job.finished_at,
+            series_id: job.series_id,
+            stable_id: job.stable_id,
+            failure_reason: job.failure_reason,
+            retry_policy: job.retry_policy,
+            retries_left: job.retries_left,
+            msg_policies: job.msg_policies,
+            msg_results: job.msg_results,
+            branches: job.branches,
+            else_msgs: job.else_msgs,
+            output_bindings: job.output_bindings,
+        }),
+        Some(_) => Err(ContractError::JobAlreadyExists {}),
+    })?;
+
+    STATE.save(
+        deps.storage,
+        &State {
+            current_job_id: state.current_job_id,
+            q: state.q.checked_add(Uint64::new(1))?,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "resume_job")
+        .add_attribute("job_id", job.id)
+        .add_attribute("job_owner", job.owner)
+        .add_attribute("job_status", serde_json_wasm::to_string(&job.status)?)
+        .add_attribute("job_last_updated_time", job.last_update_time))
+}
+
+pub fn prune_finished_jobs(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: PruneFinishedJobsMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let retention = config
+        .finished_job_retention
+        .ok_or(ContractError::PruningDisabled {})?;
+
+    let is_keeper = KEEPERS().has(deps.storage, info.sender.clone());
+    if info.sender != config.owner && !is_keeper {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if data.limit == 0 || data.limit as usize > MAX_BATCH_SIZE {
+        return Err(ContractError::InvalidBatchSize {
+            max: MAX_BATCH_SIZE,
+        });
+    }
+
+    let job_keys = FINISHED_JOBS()
+        .keys(
+            deps.storage,
+            data.start_after.map(|id| Bound::exclusive(id.u64())),
+            None,
+            Order::Ascending,
+        )
+        .take(data.limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut pruned = vec![];
+    for job_key in &job_keys {
+        let job = FINISHED_JOBS().load(deps.storage, *job_key)?;
+        let finished_at = job.finished_at.unwrap_or(job.last_update_time);
+        if finished_at.u64().saturating_add(retention.u64()) > env.block.time.seconds() {
+            continue;
+        }
+
+        JOB_SUMMARIES.save(deps.storage, *job_key, &job_summary(&job, finished_at))?;
+        FINISHED_JOBS().remove(deps.storage, *job_key)?;
+
+        let revisions = JOB_REVISIONS
+            .prefix(*job_key)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for revision in revisions {
+            JOB_REVISIONS.remove(deps.storage, (*job_key, revision));
+        }
+
+        pruned.push(job);
+    }
+
+    // like the eviction fee, the bounty comes out of the owner's account while it can cover it
+    let mut bounty_msgs = vec![];
+    let mut bounty_total = Uint128::zero();
+    if is_keeper && !config.prune_bounty.is_zero() {
+        let fee_asset = AssetInfo::Native(config.fee_denom.clone());
+        let mut owners: Vec<Addr> = pruned.iter().map(|job| job.owner.clone()).collect();
+        owners.sort();
+        owners.dedup();
+
+        for owner in owners {
+            let account = match ACCOUNTS().may_load(deps.storage, owner.clone())? {
+                None => continue,
+                Some(account) => account,
+            };
+            let balance = query_asset_balance(&deps.querier, &fee_asset, &account.account)?;
+            let jobs = pruned.iter().filter(|job| job.owner == owner).count() as u128;
+            let affordable = balance.u128() / config.prune_bounty.u128();
+            let bounty = config.prune_bounty * Uint128::new(jobs.min(affordable));
+
+            if !bounty.is_zero() {
+                bounty_msgs.push(account_transfer_msg(
+                    &account.account,
+                    &fee_asset,
+                    bounty,
+                    &info.sender,
+                )?);
+                bounty_total = bounty_total.checked_add(bounty)?;
+            }
+        }
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "prune_finished_jobs")
+        .add_attribute("jobs_pruned", pruned.len().to_string())
+        .add_attribute(
+            "pruned_job_ids",
+            serde_json_wasm::to_string(&pruned.iter().map(|job| job.id).collect::<Vec<_>>())?,
+        )
+        .add_attribute("prune_bounty", bounty_total)
+        .add_messages(bounty_msgs);
+    if let Some(last) = job_keys.last() {
+        res = res.add_attribute("last_job_id", last.to_string());
+    }
+
+    Ok(res)
+}
@@ -0,0 +1,112 @@
+use crate::state::{CONFIG, KEEPERS};
+use crate::ContractError;
+use controller::keeper::{BondKeeperMsg, Keeper, UnbondKeeperMsg, WithdrawKeeperStakeMsg};
+use cosmwasm_std::{BankMsg, Coin, DepsMut, Env, MessageInfo, Response, Uint128, Uint64};
+use cw_utils::must_pay;
+
+pub fn bond_keeper(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _data: BondKeeperMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount =
+        must_pay(&info, &config.fee_denom).map_err(|_e| ContractError::InvalidKeeperStake {})?;
+
+    let keeper = KEEPERS().update(deps.storage, info.sender.clone(), |k| {
+        Ok::<Keeper, ContractError>(match k {
+            None => Keeper {
+                address: info.sender.clone(),
+                stake: amount,
+                unbonding: Uint128::zero(),
+                unbonding_release_at: None,
+                executed: Uint64::zero(),
+                failed: Uint64::zero(),
+                evicted: Uint64::zero(),
+                slashed: Uint128::zero(),
+                rewards_earned: vec![],
+            },
+            Some(keeper) => Keeper {
+                stake: keeper.stake.checked_add(amount)?,
+                ..keeper
+            },
+        })
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "bond_keeper")
+        .add_attribute("keeper", keeper.address)
+        .add_attribute("keeper_bonded", amount)
+        .add_attribute("keeper_stake", keeper.stake))
+}
+
+// unbonded stake stays slashable until the unbonding period has passed
+pub fn unbond_keeper(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: UnbondKeeperMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut keeper = KEEPERS()
+        .may_load(deps.storage, info.sender.clone())?
+        .ok_or(ContractError::KeeperNotRegistered {})?;
+
+    let amount = data.amount.unwrap_or(keeper.stake);
+    if amount.is_zero() {
+        return Err(ContractError::InvalidKeeperUnbondAmount {});
+    }
+
+    keeper.stake = keeper.stake.checked_sub(amount)?;
+    keeper.unbonding = keeper.unbonding.checked_add(amount)?;
+    // every unbonding restarts the period for the whole queued amount
+    let release_at = Uint64::new(
+        env.block
+            .time
+            .plus_seconds(config.keeper_unbonding_period.u64())
+            .seconds(),
+    );
+    keeper.unbonding_release_at = Some(release_at);
+    KEEPERS().save(deps.storage, info.sender.clone(), &keeper)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unbond_keeper")
+        .add_attribute("keeper", keeper.address)
+        .add_attribute("keeper_unbonded", amount)
+        .add_attribute("keeper_stake", keeper.stake)
+        .add_attribute("keeper_unbonding", keeper.unbonding)
+        .add_attribute("keeper_unbonding_release_at", release_at))
+}
+
+pub fn withdraw_keeper_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    _data: WithdrawKeeperStakeMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut keeper = KEEPERS()
+        .may_load(deps.storage, info.sender.clone())?
+        .ok_or(ContractError::KeeperNotRegistered {})?;
+
+    match keeper.unbonding_release_at {
+        Some(release_at)
+            if !keeper.unbonding.is_zero() && release_at.u64() <= env.block.time.seconds() => {}
+        _ => return Err(ContractError::KeeperUnbondingNotReleased {}),
+    }
+
+    let amount = keeper.unbonding;
+    keeper.unbonding = Uint128::zero();
+    keeper.unbonding_release_at = None;
+    KEEPERS().save(deps.storage, info.sender.clone(), &keeper)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin::new(amount.u128(), config.fee_denom)],
+        })
+        .add_attribute("action", "withdraw_keeper_stake")
+        .add_attribute("keeper", keeper.address)
+        .add_attribute("keeper_withdrawn", amount))
+}
@@ -1,22 +1,42 @@
-use crate::state::{FINISHED_JOBS, PENDING_JOBS, QUERY_PAGE_SIZE};
+use crate::state::{
+    job_status_key, JobIndexes, CONFIG, FINISHED_JOBS, JOB_INDEX_MIGRATION, JOB_REVISIONS,
+    JOB_RUNS, JOB_SUMMARIES, PAUSED_JOBS, PENDING_JOBS, QUERY_PAGE_SIZE,
+};
+use crate::util::branch::select_branch;
+use crate::util::dependency::{dependencies_met, dependency_graph};
 use crate::util::filter::resolve_filters;
-use controller::job::{JobResponse, JobStatus, JobsResponse, QueryJobMsg, QueryJobsMsg};
-use cosmwasm_std::{Addr, Deps, Env, Order, StdError, StdResult, Uint64};
-use cw_storage_plus::Bound;
+use crate::util::reward::effective_reward;
+use controller::account::AssetInfo;
+use controller::job::{
+    ConditionBreakdown, Job, JobBranch, JobCursor, JobDependenciesResponse, JobFees,
+    JobHistoryResponse, JobResponse, JobRevisionsResponse, JobSortKey, JobStatus,
+    JobSummaryResponse, JobsResponse, LabelFilter, QueryJobDependenciesMsg, QueryJobHistoryMsg,
+    QueryJobMsg, QueryJobRevisionsMsg, QueryJobSummaryMsg, QueryJobsMsg, SimulateJobMsg,
+    SimulateJobResponse, SortOrder,
+};
+use controller::Config;
+use cosmwasm_std::{CosmosMsg, Deps, Env, Order, StdError, StdResult, Storage, Uint128, Uint64};
+use cw_storage_plus::{Bound, IndexedMap, PrimaryKey};
+use resolver::condition::{Condition, ConditionTrace};
 
-pub fn query_job(deps: Deps, _env: Env, data: QueryJobMsg) -> StdResult<JobResponse> {
+pub fn query_job(deps: Deps, env: Env, data: QueryJobMsg) -> StdResult<JobResponse> {
     let job = if FINISHED_JOBS().has(deps.storage, data.id.u64()) {
         FINISHED_JOBS().load(deps.storage, data.id.u64())?
+    } else if PAUSED_JOBS().has(deps.storage, data.id.u64()) {
+        PAUSED_JOBS().load(deps.storage, data.id.u64())?
     } else {
         PENDING_JOBS().load(deps.storage, data.id.u64())?
     };
-    Ok(JobResponse { job })
+    Ok(JobResponse {
+        effective_reward: effective_reward(&job, env.block.time.seconds()),
+        job,
+    })
 }
 
 pub fn query_jobs(deps: Deps, env: Env, data: QueryJobsMsg) -> StdResult<JobsResponse> {
     if !data.valid_query() {
         return Err(StdError::generic_err(
-            "Invalid query input. Must supply at most one of ids, name, or owner params.",
+            "Invalid query input. Lower bound of a range filter must not exceed its upper bound.",
         ));
     }
 
@@ -29,29 +49,9 @@ pub fn query_jobs(deps: Deps, env: Env, data: QueryJobsMsg) -> StdResult<JobsRes
         )));
     }
 
-    match data {
-        QueryJobsMsg {
-            ids: Some(ids),
-            job_status,
-            ..
-        } => query_jobs_by_ids(deps, env, ids, job_status),
-        QueryJobsMsg {
-            active: _,
-            name,
-            owner,
-            job_status,
-            start_after,
-            limit: _,
-            ..
-        } => query_jobs_by_reward(
-            deps,
-            env,
-            name,
-            owner,
-            job_status,
-            start_after.map(|i| (i._0.u128(), i._1.u64())),
-            page_size as usize,
-        ),
+    match &data.ids {
+        Some(ids) => query_jobs_by_ids(deps, env, ids.clone(), &data, page_size as usize),
+        None => query_jobs_by_filters(deps, env, &data, page_size as usize),
     }
 }
 
@@ -59,7 +59,8 @@ pub fn query_jobs_by_ids(
     deps: Deps,
     env: Env,
     ids: Vec<Uint64>,
-    job_status: Option<JobStatus>,
+    filters: &QueryJobsMsg,
+    limit: usize,
 ) -> StdResult<JobsResponse> {
     if ids.len() > QUERY_PAGE_SIZE as usize {
         return Err(StdError::generic_err(
@@ -67,66 +68,548 @@ pub fn query_jobs_by_ids(
         ));
     }
 
+    let config = CONFIG.load(deps.storage)?;
+    let sort_by = sort_key(filters);
+    let order = sort_order(filters);
+
     let mut jobs = vec![];
     for id in ids {
         let query_msg = QueryJobMsg { id };
 
         let job = query_job(deps, env.clone(), query_msg)?.job;
-        if resolve_filters(
-            deps,
-            env.clone(),
-            job.clone(),
-            None,
-            None,
-            job_status.clone(),
-        ) {
+        if resolve_filters(deps, &env, &config, &job, filters) {
             jobs.push(job)
         }
     }
-    Ok(JobsResponse {
-        jobs: jobs.clone(),
-        total_count: jobs.len(),
-    })
+    sort_jobs(&mut jobs, &sort_by, order);
+    jobs.dedup_by_key(|job| job.id);
+    let total_count = Some(jobs.len());
+
+    if let Some(cursor) = &filters.start_after {
+        let cursor = (cursor.value.u128(), cursor.id.u64());
+        jobs.retain(|job| match order {
+            Order::Ascending => position(job, &sort_by) > cursor,
+            Order::Descending => position(job, &sort_by) < cursor,
+        });
+    }
+    jobs.truncate(limit);
+
+    Ok(page(&env, jobs, limit, total_count, &sort_by))
 }
 
-pub fn query_jobs_by_reward(
+pub fn query_jobs_by_filters(
     deps: Deps,
     env: Env,
-    name: Option<String>,
-    owner: Option<Addr>,
-    job_status: Option<JobStatus>,
-    start_after: Option<(u128, u64)>,
+    filters: &QueryJobsMsg,
     limit: usize,
 ) -> StdResult<JobsResponse> {
-    let start = start_after.map(Bound::exclusive);
-    let map = if job_status.is_some() && job_status.clone().unwrap() != JobStatus::Pending {
-        FINISHED_JOBS()
+    let config = CONFIG.load(deps.storage)?;
+    let jobs_map = job_map(&filters.job_status);
+    let sort_by = sort_key(filters);
+    let order = sort_order(filters);
+
+    let labels = driving_labels(filters, &sort_by);
+    let mut jobs = vec![];
+    for label in &labels {
+        jobs.extend(take_matching(
+            deps,
+            &env,
+            &config,
+            filters,
+            candidates(
+                deps.storage,
+                jobs_map,
+                filters,
+                label.clone(),
+                filters.start_after.as_ref(),
+            )?,
+            limit,
+        )?);
+    }
+    // the first matches of each label cover the first matches of the union
+    if labels.len() > 1 {
+        sort_jobs(&mut jobs, &sort_by, order);
+        jobs.dedup_by_key(|job| job.id);
+        jobs.truncate(limit);
+    }
+
+    let migrating = JOB_INDEX_MIGRATION.may_load(deps.storage)?.is_some();
+    let total_count = if counters_cover(filters) && !migrating {
+        let status = filters.job_status.clone().unwrap_or(JobStatus::Pending);
+        Some(
+            jobs_map()
+                .idx
+                .count
+                .count(deps.storage, filters.owner.as_ref(), &status)? as usize,
+        )
     } else {
-        PENDING_JOBS()
+        None
     };
-    let infos = map
-        .idx
-        .reward
-        .range(deps.storage, None, start, Order::Descending)
-        .filter(|h| {
-            resolve_filters(
-                deps,
-                env.clone(),
-                h.as_ref().unwrap().clone().1,
-                name.clone(),
-                owner.clone(),
-                job_status.clone(),
+
+    Ok(page(&env, jobs, limit, total_count, &sort_by))
+}
+
+type JobMap = fn() -> IndexedMap<'static, u64, Job, JobIndexes<'static>>;
+
+fn job_map(job_status: &Option<JobStatus>) -> JobMap {
+    match job_status {
+        None | Some(JobStatus::Pending) => PENDING_JOBS,
+        Some(JobStatus::Paused) => PAUSED_JOBS,
+        Some(_) => FINISHED_JOBS,
+    }
+}
+
+fn sort_key(filters: &QueryJobsMsg) -> JobSortKey {
+    filters.sort_by.clone().unwrap_or(JobSortKey::Reward)
+}
+
+fn sort_order(filters: &QueryJobsMsg) -> Order {
+    match filters.order {
+        Some(SortOrder::Asc) => Order::Ascending,
+        _ => Order::Descending,
+    }
+}
+
+fn position(job: &Job, sort_by: &JobSortKey) -> (u128, u64) {
+    let value = match sort_by {
+        JobSortKey::Reward => job.reward.u128(),
+        JobSortKey::Id => job.id.u64().into(),
+        JobSortKey::UpdateTime => job.last_update_time.u64().into(),
+    };
+    (value, job.id.u64())
+}
+
+fn sort_jobs(jobs: &mut [Job], sort_by: &JobSortKey, order: Order) {
+    jobs.sort_by_key(|job| position(job, sort_by));
+    if let Order::Descending = order {
+        jobs.reverse();
+    }
+}
+
+fn page(
+    env: &Env,
+    jobs: Vec<Job>,
+    limit: usize,
+    total_count: Option<usize>,
+    sort_by: &JobSortKey,
+) -> JobsResponse {
+    let next_cursor = match jobs.last() {
+        Some(job) if jobs.len() == limit => {
+            let (value, id) = position(job, sort_by);
+            Some(JobCursor {
+                value: Uint128::new(value),
+                id: Uint64::new(id),
+            })
+        }
+        _ => None,
+    };
+
+    JobsResponse {
+        effective_rewards: jobs
+            .iter()
+            .map(|job| effective_reward(job, env.block.time.seconds()))
+            .collect(),
+        jobs,
+        total_count,
+        next_cursor,
+    }
+}
+
+// counters only track status and owner, there is no count for any other filter
+fn counters_cover(filters: &QueryJobsMsg) -> bool {
+    filters.active.is_none()
+        && filters.name.is_none()
+        && filters.name_prefix.is_none()
+        && filters.labels.is_none()
+        && filters.condition_status.is_none()
+        && filters.recurring.is_none()
+        && filters.min_reward.is_none()
+        && filters.max_reward.is_none()
+        && filters.updated_after.is_none()
+        && filters.updated_before.is_none()
+}
+
+// labels whose index the walk goes through, a single None walks without a label index
+fn driving_labels(filters: &QueryJobsMsg, sort_by: &JobSortKey) -> Vec<Option<String>> {
+    if filters.owner.is_some() || *sort_by != JobSortKey::Reward {
+        return vec![None];
+    }
+
+    match &filters.labels {
+        Some(LabelFilter::Any(labels)) => labels.iter().cloned().map(Some).collect(),
+        Some(LabelFilter::All(labels)) if !labels.is_empty() => vec![Some(labels[0].clone())],
+        _ => vec![None],
+    }
+}
+
+// tightest bounds from the inclusive range filters and the exclusive cursor
+fn bounds<'a, K: PrimaryKey<'a> + Ord>(
+    lower: Option<K>,
+    upper: Option<K>,
+    cursor: Option<K>,
+    order: Order,
+) -> (Option<Bound<'a, K>>, Option<Bound<'a, K>>) {
+    match (order, cursor) {
+        (Order::Ascending, Some(cursor))
+            if lower.as_ref().map(|l| cursor >= *l).unwrap_or(true) =>
+        {
+            (Some(Bound::exclusive(cursor)), upper.map(Bound::inclusive))
+        }
+        (Order::Descending, Some(cursor))
+            if upper.as_ref().map(|u| cursor <= *u).unwrap_or(true) =>
+        {
+            (lower.map(Bound::inclusive), Some(Bound::exclusive(cursor)))
+        }
+        _ => (lower.map(Bound::inclusive), upper.map(Bound::inclusive)),
+    }
+}
+
+// jobs in sort order within the range filters, through the narrowest index the filters allow.
+// owner and label indexes are ordered by reward, so they only narrow walks sorted by reward
+fn candidates<'c>(
+    store: &'c dyn Storage,
+    jobs_map: JobMap,
+    filters: &QueryJobsMsg,
+    label: Option<String>,
+    cursor: Option<&JobCursor>,
+) -> StdResult<Box<dyn Iterator<Item = StdResult<Job>> + 'c>> {
+    let order = sort_order(filters);
+    let map = jobs_map();
+
+    Ok(match sort_key(filters) {
+        JobSortKey::Reward => {
+            let (min, max) = bounds(
+                filters.min_reward.map(|reward| (reward.u128(), 0)),
+                filters.max_reward.map(|reward| (reward.u128(), u64::MAX)),
+                cursor.map(|cursor| (cursor.value.u128(), cursor.id.u64())),
+                order,
+            );
+            match (&filters.owner, &filters.job_status, label) {
+                (Some(owner), Some(job_status), _) => Box::new(
+                    map.idx
+                        .owner_status
+                        .sub_prefix((owner.clone(), job_status_key(job_status)))
+                        .range(store, min, max, order)
+                        .map(|r| r.map(|(_, job)| job)),
+                ),
+                (Some(owner), None, _) => Box::new(
+                    map.idx
+                        .owner
+                        .sub_prefix(owner.clone())
+                        .range(store, min, max, order)
+                        .map(|r| r.map(|(_, job)| job)),
+                ),
+                (None, _, Some(label)) => Box::new(
+                    map.idx
+                        .label
+                        .keys(store, label, min, max, order)
+                        .map(move |k| k.and_then(|(_, id)| jobs_map().load(store, id))),
+                ),
+                (None, _, None) => Box::new(
+                    map.idx
+                        .reward
+                        .range(store, min, max, order)
+                        .map(|r| r.map(|(_, job)| job)),
+                ),
+            }
+        }
+        JobSortKey::Id => {
+            let (min, max) = bounds(None, None, cursor.map(|cursor| cursor.id.u64()), order);
+            Box::new(
+                map.range(store, min, max, order)
+                    .map(|r| r.map(|(_, job)| job)),
             )
-        })
-        .take(limit)
-        .collect::<StdResult<Vec<_>>>()?;
+        }
+        JobSortKey::UpdateTime => {
+            let cursor = match cursor {
+                None => None,
+                Some(cursor) => Some((
+                    u64::try_from(cursor.value.u128())
+                        .map_err(|_| StdError::generic_err("Invalid cursor."))?,
+                    cursor.id.u64(),
+                )),
+            };
+            let (min, max) = bounds(
+                filters.updated_after.map(|time| (time.u64(), 0)),
+                filters.updated_before.map(|time| (time.u64(), u64::MAX)),
+                cursor,
+                order,
+            );
+            Box::new(
+                map.idx
+                    .publish_time
+                    .range(store, min, max, order)
+                    .map(|r| r.map(|(_, job)| job)),
+            )
+        }
+    })
+}
 
+fn take_matching(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    filters: &QueryJobsMsg,
+    candidates: impl Iterator<Item = StdResult<Job>>,
+    limit: usize,
+) -> StdResult<Vec<Job>> {
     let mut jobs = vec![];
-    for info in infos.clone() {
-        jobs.push(info.1);
+    for job in candidates {
+        let job = job?;
+        if resolve_filters(deps, env, config, &job, filters) {
+            jobs.push(job);
+            if jobs.len() == limit {
+                break;
+            }
+        }
     }
-    Ok(JobsResponse {
-        jobs,
-        total_count: infos.len(),
+
+    Ok(jobs)
+}
+
+pub fn query_job_dependencies(
+    deps: Deps,
+    env: Env,
+    data: QueryJobDependenciesMsg,
+) -> StdResult<JobDependenciesResponse> {
+    let job = query_job(deps, env, QueryJobMsg { id: data.id })?.job;
+    let satisfied = dependencies_met(deps.storage, &job)?;
+
+    Ok(JobDependenciesResponse {
+        nodes: dependency_graph(deps.storage, job)?,
+        satisfied,
+    })
+}
+
+pub fn query_job_revisions(
+    deps: Deps,
+    _env: Env,
+    data: QueryJobRevisionsMsg,
+) -> StdResult<JobRevisionsResponse> {
+    let page_size = data.limit.unwrap_or(QUERY_PAGE_SIZE);
+
+    if page_size > QUERY_PAGE_SIZE {
+        return Err(StdError::generic_err(format!(
+            "Limit must be a max of {}.",
+            QUERY_PAGE_SIZE
+        )));
+    }
+
+    let start = data.start_after.map(|r| Bound::exclusive(r.u64()));
+    let revisions = JOB_REVISIONS
+        .prefix(data.id.u64())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(page_size as usize)
+        .map(|r| r.map(|(_, revision)| revision))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(JobRevisionsResponse { revisions })
+}
+
+pub fn query_job_history(
+    deps: Deps,
+    _env: Env,
+    data: QueryJobHistoryMsg,
+) -> StdResult<JobHistoryResponse> {
+    let page_size = data.limit.unwrap_or(QUERY_PAGE_SIZE);
+
+    if page_size > QUERY_PAGE_SIZE {
+        return Err(StdError::generic_err(format!(
+            "Limit must be a max of {}.",
+            QUERY_PAGE_SIZE
+        )));
+    }
+
+    let start = data.start_after.map(|r| Bound::exclusive(r.u64()));
+    let runs = JOB_RUNS
+        .prefix(data.series_id.u64())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(page_size as usize)
+        .map(|r| r.map(|(_, run)| run))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(JobHistoryResponse { runs })
+}
+
+pub fn query_simulate_job(
+    deps: Deps,
+    _env: Env,
+    data: SimulateJobMsg,
+) -> StdResult<SimulateJobResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let job = data.job;
+
+    let _validate_conditions_and_variables: Option<String> = deps.querier.query_wasm_smart(
+        config.resolver_address.clone(),
+        &resolver::QueryMsg::QueryValidateJobCreation(resolver::QueryValidateJobCreationMsg {
+            condition: job.condition.clone(),
+            terminate_condition: job.terminate_condition.clone(),
+            vars: job.vars.clone(),
+            msgs: job.msgs.clone(),
+            branches: job.branches.clone().unwrap_or_default(),
+            else_msgs: job.else_msgs.clone(),
+        }),
+    )?;
+
+    let vars: String = deps.querier.query_wasm_smart(
+        config.resolver_address.clone(),
+        &resolver::QueryMsg::QueryHydrateVars(resolver::QueryHydrateVarsMsg {
+            vars: job.vars,
+            external_inputs: data.external_inputs,
+        }),
+    )?;
+
+    let mut condition_breakdown = vec![];
+    let (condition, condition_error) = match serde_json_wasm::from_str::<Condition>(&job.condition)
+    {
+        Ok(parsed) => {
+            let trace: StdResult<ConditionTrace> = deps.querier.query_wasm_smart(
+                config.resolver_address.clone(),
+                &resolver::QueryMsg::QueryResolveConditionTrace(
+                    resolver::QueryResolveConditionTraceMsg {
+                        condition: job.condition.clone(),
+                        vars: vars.clone(),
+                    },
+                ),
+            );
+            match trace {
+                Ok(trace) => {
+                    trace_breakdown(&parsed, &trace, "".to_string(), &mut condition_breakdown)?;
+                    (Some(trace.result()), None)
+                }
+                Err(e) => {
+                    condition_breakdown.push(ConditionBreakdown {
+                        path: "".to_string(),
+                        condition: job.condition.clone(),
+                        result: None,
+                        error: Some(e.to_string()),
+                    });
+                    (None, Some(e.to_string()))
+                }
+            }
+        }
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    // msgs of the branch that would be sent, none when the condition fails or no branch fires
+    let branch_msgs = match condition_error {
+        Some(_) => None,
+        None => {
+            let mut branches = vec![JobBranch {
+                condition: job.condition.clone(),
+                msgs: job.msgs.clone(),
+            }];
+            branches.extend(job.branches.unwrap_or_default());
+            select_branch(deps, &config, &branches, &job.else_msgs, &vars)?
+        }
+    };
+
+    let msgs: Vec<CosmosMsg> = match branch_msgs {
+        None => vec![],
+        Some(branch_msgs) => deps.querier.query_wasm_smart(
+            config.resolver_address.clone(),
+            &resolver::QueryMsg::QueryHydrateMsgs(resolver::QueryHydrateMsgsMsg {
+                msgs: branch_msgs,
+                vars: vars.clone(),
+            }),
+        )?,
+    };
+
+    let reward_asset = job
+        .reward_asset
+        .unwrap_or_else(|| AssetInfo::Native(config.fee_denom.clone()));
+    let creation_fee =
+        job.reward * Uint128::from(config.creation_fee_percentage) / Uint128::new(100);
+    let gas_budget = match &job.gas_allowance {
+        None => Uint128::zero(),
+        Some(gas_allowance) => gas_allowance.budget,
+    };
+
+    Ok(SimulateJobResponse {
+        vars,
+        condition,
+        condition_error,
+        condition_breakdown,
+        msgs,
+        fees: JobFees {
+            reward_asset,
+            reward: job.reward,
+            creation_fee,
+            gas_budget,
+            total: job
+                .reward
+                .checked_add(creation_fee)?
+                .checked_add(gas_budget)?,
+        },
+    })
+}
+
+// lists every node the resolver evaluated, depth first, skipping short-circuited ones
+fn trace_breakdown(
+    condition: &Condition,
+    trace: &ConditionTrace,
+    path: String,
+    breakdown: &mut Vec<ConditionBreakdown>,
+) -> StdResult<()> {
+    breakdown.push(ConditionBreakdown {
+        path: path.clone(),
+        condition: serde_json_wasm::to_string(condition)
+            .map_err(|e| StdError::generic_err(e.to_string()))?,
+        result: Some(trace.result()),
+        error: None,
+    });
+
+    let children: Vec<(&Condition, &ConditionTrace)> = match (condition, trace) {
+        (
+            Condition::And(conditions),
+            ConditionTrace::And {
+                conditions: traces, ..
+            },
+        )
+        | (
+            Condition::Or(conditions),
+            ConditionTrace::Or {
+                conditions: traces, ..
+            },
+        ) => conditions
+            .iter()
+            .map(|c| c.as_ref())
+            .zip(traces.iter())
+            .collect(),
+        (
+            Condition::Not(condition),
+            ConditionTrace::Not {
+                condition: trace, ..
+            },
+        ) => {
+            vec![(condition, trace)]
+        }
+        (Condition::Expr(_), ConditionTrace::Expr(_)) => vec![],
+        _ => {
+            return Err(StdError::generic_err(
+                "Condition trace does not match the condition.",
+            ))
+        }
+    };
+
+    for (i, (child, child_trace)) in children.into_iter().enumerate() {
+        let child_path = if path.is_empty() {
+            i.to_string()
+        } else {
+            format!("{}.{}", path, i)
+        };
+        trace_breakdown(child, child_trace, child_path, breakdown)?;
+    }
+
+    Ok(())
+}
+
+pub fn query_job_summary(
+    deps: Deps,
+    _env: Env,
+    data: QueryJobSummaryMsg,
+) -> StdResult<JobSummaryResponse> {
+    Ok(JobSummaryResponse {
+        summary: JOB_SUMMARIES.load(deps.storage, data.id.u64())?,
     })
 }
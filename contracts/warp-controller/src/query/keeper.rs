@@ -0,0 +1,40 @@
+use crate::state::{KEEPERS, QUERY_PAGE_SIZE};
+use controller::keeper::{KeeperResponse, KeepersResponse, QueryKeeperMsg, QueryKeepersMsg};
+use cosmwasm_std::{Deps, Env, Order, StdError, StdResult};
+use cw_storage_plus::Bound;
+
+pub fn query_keeper(deps: Deps, _env: Env, data: QueryKeeperMsg) -> StdResult<KeeperResponse> {
+    Ok(KeeperResponse {
+        keeper: KEEPERS().load(deps.storage, deps.api.addr_validate(data.address.as_str())?)?,
+    })
+}
+
+// ranked by executed jobs, most active keepers first
+pub fn query_keepers(deps: Deps, _env: Env, data: QueryKeepersMsg) -> StdResult<KeepersResponse> {
+    let page_size = data.limit.unwrap_or(QUERY_PAGE_SIZE);
+
+    if page_size > QUERY_PAGE_SIZE {
+        return Err(StdError::generic_err(format!(
+            "Limit must be a max of {}.",
+            QUERY_PAGE_SIZE
+        )));
+    }
+
+    let start_after = match data.start_after {
+        None => None,
+        Some(index) => Some(Bound::exclusive((
+            index.executed.u64(),
+            deps.api.addr_validate(index.address.as_str())?,
+        ))),
+    };
+
+    let keepers = KEEPERS()
+        .idx
+        .executed
+        .range(deps.storage, None, start_after, Order::Descending)
+        .take(page_size as usize)
+        .map(|k| k.map(|(_, keeper)| keeper))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(KeepersResponse { keepers })
+}
@@ -1,22 +1,166 @@
 use controller::account::Account;
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, UniqueIndex};
+use controller::keeper::Keeper;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Empty, Order, StdResult, Storage, Uint128, Uint64};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 
-use controller::job::Job;
+use controller::job::{Job, JobRevision, JobRun, JobStatus, JobSummary};
 use controller::{Config, State};
 
+// owner, owner and status, and label indexes carry the reward so their entries
+// come out in the same (reward, id) order as the reward index
 pub struct JobIndexes<'a> {
     pub reward: UniqueIndex<'a, (u128, u64), Job>,
     pub publish_time: MultiIndex<'a, u64, Job, u64>,
+    pub owner: MultiIndex<'a, (Addr, u128), Job, u64>,
+    pub owner_status: MultiIndex<'a, (Addr, String, u128), Job, u64>,
+    pub label: LabelIndex<'a>,
+    pub count: JobCountIndex<'a>,
 }
 
 impl IndexList<Job> for JobIndexes<'_> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Job>> + '_> {
-        let v: Vec<&dyn Index<Job>> = vec![&self.reward, &self.publish_time];
+        let v: Vec<&dyn Index<Job>> = vec![
+            &self.reward,
+            &self.publish_time,
+            &self.owner,
+            &self.owner_status,
+            &self.label,
+            &self.count,
+        ];
         Box::new(v.into_iter())
     }
 }
 
+// a MultiIndex holds a single key per job, this one holds a (label, reward, id) entry per label
+pub struct LabelIndex<'a> {
+    entries: Map<'a, (String, u128, u64), Empty>,
+}
+
+impl<'a> LabelIndex<'a> {
+    pub const fn new(namespace: &'a str) -> Self {
+        LabelIndex {
+            entries: Map::new(namespace),
+        }
+    }
+
+    pub fn keys<'c>(
+        &self,
+        store: &'c dyn Storage,
+        label: String,
+        min: Option<Bound<'a, (u128, u64)>>,
+        max: Option<Bound<'a, (u128, u64)>>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<(u128, u64)>> + 'c> {
+        self.entries.sub_prefix(label).keys(store, min, max, order)
+    }
+}
+
+impl Index<Job> for LabelIndex<'_> {
+    fn save(&self, store: &mut dyn Storage, _pk: &[u8], job: &Job) -> StdResult<()> {
+        for label in &job.labels {
+            self.entries.save(
+                store,
+                (label.clone(), job.reward.u128(), job.id.u64()),
+                &Empty {},
+            )?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, store: &mut dyn Storage, _pk: &[u8], old_job: &Job) -> StdResult<()> {
+        for label in &old_job.labels {
+            self.entries.remove(
+                store,
+                (label.clone(), old_job.reward.u128(), old_job.id.u64()),
+            );
+        }
+        Ok(())
+    }
+}
+
+// job counts by status and by owner and status, kept up to date by every write to the map.
+// jobs stored before counting started are picked up the first time they are saved again
+pub struct JobCountIndex<'a> {
+    counted: Map<'a, u64, Empty>,
+    by_status: Map<'a, String, u64>,
+    by_owner_status: Map<'a, (Addr, String), u64>,
+}
+
+impl<'a> JobCountIndex<'a> {
+    pub const fn new(
+        counted_namespace: &'a str,
+        status_namespace: &'a str,
+        owner_status_namespace: &'a str,
+    ) -> Self {
+        JobCountIndex {
+            counted: Map::new(counted_namespace),
+            by_status: Map::new(status_namespace),
+            by_owner_status: Map::new(owner_status_namespace),
+        }
+    }
+
+    pub fn count(
+        &self,
+        store: &dyn Storage,
+        owner: Option<&Addr>,
+        status: &JobStatus,
+    ) -> StdResult<u64> {
+        let count = match owner {
+            None => self.by_status.may_load(store, job_status_key(status))?,
+            Some(owner) => self
+                .by_owner_status
+                .may_load(store, (owner.clone(), job_status_key(status)))?,
+        };
+        Ok(count.unwrap_or_default())
+    }
+
+    fn add(&self, store: &mut dyn Storage, job: &Job, delta: i64) -> StdResult<()> {
+        let apply = |count: Option<u64>| -> StdResult<u64> {
+            let count = count.unwrap_or_default();
+            Ok(if delta < 0 {
+                count.saturating_sub(delta.unsigned_abs())
+            } else {
+                count + delta as u64
+            })
+        };
+        let status = job_status_key(&job.status);
+        self.by_status.update(store, status.clone(), apply)?;
+        self.by_owner_status
+            .update(store, (job.owner.clone(), status), apply)?;
+        Ok(())
+    }
+}
+
+impl Index<Job> for JobCountIndex<'_> {
+    fn save(&self, store: &mut dyn Storage, _pk: &[u8], job: &Job) -> StdResult<()> {
+        self.counted.save(store, job.id.u64(), &Empty {})?;
+        self.add(store, job, 1)
+    }
+
+    fn remove(&self, store: &mut dyn Storage, _pk: &[u8], old_job: &Job) -> StdResult<()> {
+        if !self.counted.has(store, old_job.id.u64()) {
+            return Ok(());
+        }
+        self.counted.remove(store, old_job.id.u64());
+        self.add(store, old_job, -1)
+    }
+}
+
+pub fn job_status_key(status: &JobStatus) -> String {
+    match status {
+        JobStatus::Pending => "pending",
+        JobStatus::Executed => "executed",
+        JobStatus::Failed => "failed",
+        JobStatus::Cancelled => "cancelled",
+        JobStatus::Evicted => "evicted",
+        JobStatus::Expired => "expired",
+        JobStatus::Paused => "paused",
+        JobStatus::Completed => "completed",
+    }
+    .to_string()
+}
+
 #[allow(non_snake_case)]
 pub fn PENDING_JOBS<'a>() -> IndexedMap<'a, u64, Job, JobIndexes<'a>> {
     let indexes = JobIndexes {
@@ -29,6 +173,28 @@ pub fn PENDING_JOBS<'a>() -> IndexedMap<'a, u64, Job, JobIndexes<'a>> {
             "pending_jobs_v3",
             "pending_jobs__publish_timestamp_v3",
         ),
+        owner: MultiIndex::new(
+            |_pk, job| (job.owner.clone(), job.reward.u128()),
+            "pending_jobs_v3",
+            "pending_jobs__owner_v3",
+        ),
+        owner_status: MultiIndex::new(
+            |_pk, job| {
+                (
+                    job.owner.clone(),
+                    job_status_key(&job.status),
+                    job.reward.u128(),
+                )
+            },
+            "pending_jobs_v3",
+            "pending_jobs__owner_status_v3",
+        ),
+        label: LabelIndex::new("pending_jobs__label_v3"),
+        count: JobCountIndex::new(
+            "pending_jobs__counted_v3",
+            "pending_jobs__count_v3",
+            "pending_jobs__owner_count_v3",
+        ),
     };
     IndexedMap::new("pending_jobs_v3", indexes)
 }
@@ -45,10 +211,71 @@ pub fn FINISHED_JOBS<'a>() -> IndexedMap<'a, u64, Job, JobIndexes<'a>> {
             "finished_jobs_v3",
             "finished_jobs__publish_timestamp_v3",
         ),
+        owner: MultiIndex::new(
+            |_pk, job| (job.owner.clone(), job.reward.u128()),
+            "finished_jobs_v3",
+            "finished_jobs__owner_v3",
+        ),
+        owner_status: MultiIndex::new(
+            |_pk, job| {
+                (
+                    job.owner.clone(),
+                    job_status_key(&job.status),
+                    job.reward.u128(),
+                )
+            },
+            "finished_jobs_v3",
+            "finished_jobs__owner_status_v3",
+        ),
+        label: LabelIndex::new("finished_jobs__label_v3"),
+        count: JobCountIndex::new(
+            "finished_jobs__counted_v3",
+            "finished_jobs__count_v3",
+            "finished_jobs__owner_count_v3",
+        ),
     };
     IndexedMap::new("finished_jobs_v3", indexes)
 }
 
+// paused jobs are kept apart so keepers scanning the pending reward index never see them
+#[allow(non_snake_case)]
+pub fn PAUSED_JOBS<'a>() -> IndexedMap<'a, u64, Job, JobIndexes<'a>> {
+    let indexes = JobIndexes {
+        reward: UniqueIndex::new(
+            |job| (job.reward.u128(), job.id.u64()),
+            "paused_jobs__reward",
+        ),
+        publish_time: MultiIndex::new(
+            |_pk, job| job.last_update_time.u64(),
+            "paused_jobs",
+            "paused_jobs__publish_timestamp",
+        ),
+        owner: MultiIndex::new(
+            |_pk, job| (job.owner.clone(), job.reward.u128()),
+            "paused_jobs",
+            "paused_jobs__owner",
+        ),
+        owner_status: MultiIndex::new(
+            |_pk, job| {
+                (
+                    job.owner.clone(),
+                    job_status_key(&job.status),
+                    job.reward.u128(),
+                )
+            },
+            "paused_jobs",
+            "paused_jobs__owner_status",
+        ),
+        label: LabelIndex::new("paused_jobs__label"),
+        count: JobCountIndex::new(
+            "paused_jobs__counted",
+            "paused_jobs__count",
+            "paused_jobs__owner_count",
+        ),
+    };
+    IndexedMap::new("paused_jobs", indexes)
+}
+
 pub struct AccountIndexes<'a> {
     pub account: UniqueIndex<'a, Addr, Account>,
 }
@@ -68,6 +295,47 @@ pub fn ACCOUNTS<'a>() -> IndexedMap<'a, Addr, Account, AccountIndexes<'a>> {
     IndexedMap::new("accounts", indexes)
 }
 
+pub struct KeeperIndexes<'a> {
+    pub executed: UniqueIndex<'a, (u64, Addr), Keeper>,
+}
+
+impl IndexList<Keeper> for KeeperIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Keeper>> + '_> {
+        let v: Vec<&dyn Index<Keeper>> = vec![&self.executed];
+        Box::new(v.into_iter())
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn KEEPERS<'a>() -> IndexedMap<'a, Addr, Keeper, KeeperIndexes<'a>> {
+    let indexes = KeeperIndexes {
+        executed: UniqueIndex::new(
+            |keeper| (keeper.executed.u64(), keeper.address.clone()),
+            "keepers__executed",
+        ),
+    };
+    IndexedMap::new("keepers", indexes)
+}
+
+// keeper that dispatched a job and the reward it was paid, read back in reply
+#[cw_serde]
+pub struct JobDispatch {
+    pub keeper: Addr,
+    pub reward: Uint128,
+}
+
+pub const JOB_DISPATCHES: Map<u64, JobDispatch> = Map::new("job_dispatches");
+// (series id, run), runs count up from 1 for every execution of a job in the series
+pub const JOB_RUNS: Map<(u64, u64), JobRun> = Map::new("job_runs");
+
 pub const QUERY_PAGE_SIZE: u32 = 50;
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const STATE: Item<State> = Item::new("state");
+// (job id, revision), revisions count up from 1 for every update that changed the job
+pub const JOB_REVISIONS: Map<(u64, u64), JobRevision> = Map::new("job_revisions");
+// finished jobs that were pruned
+pub const JOB_SUMMARIES: Map<u64, JobSummary> = Map::new("job_summaries");
+
+// set by an upgrade to the last job id MigrateJobIndexes has rewritten since, the job counters
+// miss jobs stored before the upgrade until a pass has covered every job
+pub const JOB_INDEX_MIGRATION: Item<Option<Uint64>> = Item::new("job_index_migration");
@@ -1 +1,2 @@
+mod test_migrate_job_indexes;
 mod test_update_config;
@@ -0,0 +1,145 @@
+use crate::execute::controller::migrate_job_indexes;
+use crate::query::job::query_jobs;
+use crate::state::{FINISHED_JOBS, JOB_INDEX_MIGRATION, PENDING_JOBS};
+use crate::tests::helpers::{mock_controller, mock_job};
+use crate::ContractError;
+use controller::job::{Job, JobStatus, LabelFilter, QueryJobsMsg};
+use controller::MigrateJobsMsg;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{Addr, Uint64};
+use cw_storage_plus::Map;
+
+fn owner_query(owner: &str, job_status: Option<JobStatus>) -> QueryJobsMsg {
+    QueryJobsMsg {
+        ids: None,
+        active: None,
+        owner: Some(Addr::unchecked(owner)),
+        name: None,
+        name_prefix: None,
+        labels: None,
+        job_status,
+        condition_status: None,
+        recurring: None,
+        min_reward: None,
+        max_reward: None,
+        updated_after: None,
+        updated_before: None,
+        sort_by: None,
+        order: None,
+        start_after: None,
+        limit: None,
+    }
+}
+
+#[test]
+fn test_migrate_job_indexes_backfills_owner_and_label_indexes() {
+    let mut deps = mock_controller();
+    // as left by the upgrade
+    JOB_INDEX_MIGRATION.save(&mut deps.storage, &None).unwrap();
+
+    // jobs stored before the owner and label indexes existed only have their primary entry
+    const LEGACY_PENDING_JOBS: Map<u64, Job> = Map::new("pending_jobs_v3");
+    for id in 1..=3u64 {
+        let mut job = mock_job(id);
+        job.labels = vec!["defi".to_string()];
+        LEGACY_PENDING_JOBS
+            .save(&mut deps.storage, id, &job)
+            .unwrap();
+    }
+
+    let res = query_jobs(deps.as_ref(), mock_env(), owner_query("owner", None)).unwrap();
+    assert!(res.jobs.is_empty());
+    // counts are withheld until the migration has covered every job
+    assert_eq!(res.total_count, None);
+
+    let err = migrate_job_indexes(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        MigrateJobsMsg {
+            start_after: None,
+            limit: 2,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let mut start_after = None;
+    loop {
+        let res = migrate_job_indexes(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            MigrateJobsMsg {
+                start_after,
+                limit: 2,
+            },
+        )
+        .unwrap();
+        match res.attributes.iter().find(|a| a.key == "last_job_id") {
+            None => break,
+            Some(a) => start_after = Some(Uint64::new(a.value.parse().unwrap())),
+        }
+    }
+
+    let res = query_jobs(deps.as_ref(), mock_env(), owner_query("owner", None)).unwrap();
+    assert_eq!(res.jobs.len(), 3);
+    assert_eq!(res.total_count, Some(3));
+    assert_eq!(JOB_INDEX_MIGRATION.may_load(&deps.storage).unwrap(), None);
+
+    let res = query_jobs(
+        deps.as_ref(),
+        mock_env(),
+        QueryJobsMsg {
+            owner: None,
+            labels: Some(LabelFilter::Any(vec!["defi".to_string()])),
+            ..owner_query("owner", None)
+        },
+    )
+    .unwrap();
+    assert_eq!(res.jobs.len(), 3);
+}
+
+#[test]
+fn test_query_jobs_by_owner_and_status() {
+    let mut deps = mock_controller();
+
+    for id in 1..=4u64 {
+        let mut job = mock_job(id);
+        job.status = if id % 2 == 0 {
+            JobStatus::Executed
+        } else {
+            JobStatus::Failed
+        };
+        if id == 4 {
+            job.owner = Addr::unchecked("other");
+        }
+        FINISHED_JOBS().save(&mut deps.storage, id, &job).unwrap();
+    }
+    PENDING_JOBS()
+        .save(&mut deps.storage, 5, &mock_job(5))
+        .unwrap();
+
+    let ids = |msg: QueryJobsMsg| -> Vec<u64> {
+        query_jobs(deps.as_ref(), mock_env(), msg)
+            .unwrap()
+            .jobs
+            .iter()
+            .map(|job| job.id.u64())
+            .collect()
+    };
+
+    assert_eq!(
+        ids(owner_query("owner", Some(JobStatus::Executed))),
+        vec![2]
+    );
+    assert_eq!(
+        ids(owner_query("owner", Some(JobStatus::Failed))),
+        vec![3, 1]
+    );
+    assert_eq!(
+        ids(owner_query("other", Some(JobStatus::Executed))),
+        vec![4]
+    );
+    assert_eq!(ids(owner_query("owner", None)), vec![5]);
+}
@@ -1 +1,147 @@
+use crate::contract::execute;
+use crate::state::CONFIG;
+use crate::tests::helpers::mock_controller;
+use crate::ContractError;
+use controller::{ExecuteMsg, UpdateConfigMsg};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{OwnedDeps, Response, Uint64};
 
+fn no_changes() -> UpdateConfigMsg {
+    UpdateConfigMsg {
+        owner: None,
+        fee_collector: None,
+        minimum_reward: None,
+        creation_fee_percentage: None,
+        cancellation_fee_percentage: None,
+        t_max: None,
+        t_min: None,
+        a_max: None,
+        a_min: None,
+        q_max: None,
+        reward_assets: None,
+        max_job_gas_limit: None,
+        clear_max_job_gas_limit: None,
+        keeper_min_stake: None,
+        keeper_slash_percentage: None,
+        keeper_unbonding_period: None,
+        finished_job_retention: None,
+        clear_finished_job_retention: None,
+        prune_bounty: None,
+    }
+}
+
+fn update_config(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    data: UpdateConfigMsg,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig(data),
+    )
+}
+
+#[test]
+fn test_update_config_clears_max_job_gas_limit() {
+    let mut deps = mock_controller();
+
+    update_config(
+        &mut deps,
+        UpdateConfigMsg {
+            max_job_gas_limit: Some(Uint64::new(1_000_000)),
+            ..no_changes()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap().max_job_gas_limit,
+        Some(Uint64::new(1_000_000))
+    );
+
+    // leaving it out keeps the limit
+    update_config(&mut deps, no_changes()).unwrap();
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap().max_job_gas_limit,
+        Some(Uint64::new(1_000_000))
+    );
+
+    update_config(
+        &mut deps,
+        UpdateConfigMsg {
+            clear_max_job_gas_limit: Some(true),
+            ..no_changes()
+        },
+    )
+    .unwrap();
+    assert_eq!(CONFIG.load(&deps.storage).unwrap().max_job_gas_limit, None);
+}
+
+#[test]
+fn test_update_config_set_and_clear_max_job_gas_limit() {
+    let mut deps = mock_controller();
+
+    let err = update_config(
+        &mut deps,
+        UpdateConfigMsg {
+            max_job_gas_limit: Some(Uint64::new(1_000_000)),
+            clear_max_job_gas_limit: Some(true),
+            ..no_changes()
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        ContractError::ConflictingConfigUpdate {
+            field: "max_job_gas_limit".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_update_config_clears_finished_job_retention() {
+    let mut deps = mock_controller();
+
+    update_config(
+        &mut deps,
+        UpdateConfigMsg {
+            finished_job_retention: Some(Uint64::new(86_400)),
+            ..no_changes()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap().finished_job_retention,
+        Some(Uint64::new(86_400))
+    );
+
+    update_config(
+        &mut deps,
+        UpdateConfigMsg {
+            clear_finished_job_retention: Some(true),
+            ..no_changes()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap().finished_job_retention,
+        None
+    );
+
+    let err = update_config(
+        &mut deps,
+        UpdateConfigMsg {
+            finished_job_retention: Some(Uint64::new(86_400)),
+            clear_finished_job_retention: Some(true),
+            ..no_changes()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ConflictingConfigUpdate {
+            field: "finished_job_retention".to_string()
+        }
+    );
+}
@@ -1,4 +1,8 @@
 mod test_create_job;
+mod test_create_jobs;
 mod test_delete_job;
+mod test_evict_job;
 mod test_execute_job;
+mod test_pause_job;
+mod test_prune_finished_jobs;
 mod test_update_job;
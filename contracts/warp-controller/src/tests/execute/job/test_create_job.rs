@@ -1,3 +1,15 @@
+use crate::contract::execute;
+use crate::state::PENDING_JOBS;
+use crate::tests::helpers::{mock_controller, mock_create_job_msg};
+use crate::ContractError;
+use controller::account::MsgPolicy;
+use controller::job::CreateJobMsg;
+use controller::ExecuteMsg;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    to_binary, ContractInfoResponse, ContractResult, OwnedDeps, Response, SystemResult, WasmQuery,
+};
+
 #[test]
 fn test_create_job_success() {}
 
@@ -24,3 +36,71 @@ fn test_create_job_name_too_short() {}
 
 #[test]
 fn test_create_job_name_too_long() {}
+
+#[test]
+fn test_create_job_stable_id_not_recurring() {
+    let mut deps = mock_controller();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::CreateJob(CreateJobMsg {
+            stable_id: Some(true),
+            recurring: false,
+            ..mock_create_job_msg("job_1", 10_000)
+        }),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::StableIdNotRecurring {});
+    assert!(!PENDING_JOBS().has(&deps.storage, 1));
+}
+
+// controller whose account was instantiated from the given code id
+fn create_with_policies(
+    account_code_id: u64,
+) -> (
+    OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    Result<Response, ContractError>,
+) {
+    let mut deps = mock_controller();
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::ContractInfo { .. } => {
+            let mut info = ContractInfoResponse::default();
+            info.code_id = account_code_id;
+            SystemResult::Ok(ContractResult::Ok(to_binary(&info).unwrap()))
+        }
+        _ => SystemResult::Ok(ContractResult::Ok(to_binary(&None::<String>).unwrap())),
+    });
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::CreateJob(CreateJobMsg {
+            msg_policies: Some(vec![MsgPolicy::Optional]),
+            ..mock_create_job_msg("job_1", 10_000)
+        }),
+    );
+    (deps, res)
+}
+
+#[test]
+fn test_create_job_msg_policies() {
+    let (deps, res) = create_with_policies(1);
+
+    res.unwrap();
+    assert_eq!(
+        PENDING_JOBS().load(&deps.storage, 1).unwrap().msg_policies,
+        Some(vec![MsgPolicy::Optional])
+    );
+}
+
+#[test]
+fn test_create_job_msg_policies_outdated_account() {
+    let (deps, res) = create_with_policies(0);
+
+    assert_eq!(res.unwrap_err(), ContractError::AccountOutdated {});
+    assert!(!PENDING_JOBS().has(&deps.storage, 1));
+}
@@ -0,0 +1,94 @@
+use crate::contract::execute;
+use crate::state::{PENDING_JOBS, STATE};
+use crate::tests::helpers::{mock_controller, mock_create_job_msg};
+use crate::ContractError;
+use account::GenericMsg;
+use controller::job::CreateJobsMsg;
+use controller::ExecuteMsg;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{to_binary, BankMsg, Coin, CosmosMsg, ReplyOn, SubMsg, Uint64, WasmMsg};
+
+#[test]
+fn test_create_jobs_success() {
+    let mut deps = mock_controller();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::CreateJobs(CreateJobsMsg {
+            jobs: vec![
+                mock_create_job_msg("job_1", 10_000),
+                mock_create_job_msg("job_2", 30_000),
+            ],
+        }),
+    )
+    .unwrap();
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.current_job_id, Uint64::new(3));
+    assert_eq!(state.q, Uint64::new(2));
+    assert_eq!(PENDING_JOBS().load(&deps.storage, 1).unwrap().name, "job_1");
+    assert_eq!(PENDING_JOBS().load(&deps.storage, 2).unwrap().name, "job_2");
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg {
+            id: 0,
+            msg: CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "account".to_string(),
+                msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
+                    msgs: vec![
+                        CosmosMsg::Bank(BankMsg::Send {
+                            to_address: mock_env().contract.address.to_string(),
+                            amount: vec![Coin::new(40_000, "uluna")],
+                        }),
+                        CosmosMsg::Bank(BankMsg::Send {
+                            to_address: "fee_collector".to_string(),
+                            amount: vec![Coin::new(2_000, "uluna")],
+                        }),
+                    ],
+                }))
+                .unwrap(),
+                funds: vec![],
+            }),
+            gas_limit: None,
+            reply_on: ReplyOn::Never,
+        }]
+    );
+}
+
+#[test]
+fn test_create_jobs_invalid_job_fails_batch() {
+    let mut deps = mock_controller();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::CreateJobs(CreateJobsMsg {
+            jobs: vec![
+                mock_create_job_msg("job_1", 10_000),
+                mock_create_job_msg("", 10_000),
+            ],
+        }),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::NameTooShort {});
+}
+
+#[test]
+fn test_create_jobs_empty_batch() {
+    let mut deps = mock_controller();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::CreateJobs(CreateJobsMsg { jobs: vec![] }),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::InvalidBatchSize { max: 50 });
+}
@@ -0,0 +1,120 @@
+use crate::contract::execute;
+use crate::state::{CONFIG, FINISHED_JOBS, PENDING_JOBS, STATE};
+use crate::tests::helpers::{mock_config, mock_controller, mock_job};
+use crate::ContractError;
+use controller::account::AssetInfo;
+use controller::job::{EvictJobMsg, Job, JobExclusivity, JobStatus};
+use controller::{Config, ExecuteMsg, State};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, OwnedDeps, Response, Uint128, Uint64};
+use cw_utils::{Duration, Expiration};
+
+fn controller_with(config: Config, job: Job) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_controller();
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+    deps
+}
+
+fn evict(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("evictor", &[]),
+        ExecuteMsg::EvictJob(EvictJobMsg { id: Uint64::one() }),
+    )
+}
+
+#[test]
+fn test_evict_job_fee_in_reward_asset() {
+    let mut config = mock_config();
+    config.a_min = Uint128::new(2_500);
+
+    let mut job = mock_job(1);
+    job.reward = Uint128::new(20_000);
+    job.reward_asset = Some(AssetInfo::Native("uusd".to_string()));
+    let mut deps = controller_with(config, job);
+
+    let res = evict(&mut deps).unwrap();
+
+    // a quarter of the minimum reward, so a quarter of this job's reward
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|msg| msg.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "evictor".to_string(),
+                amount: vec![Coin::new(5_000, "uusd")],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "account".to_string(),
+                amount: vec![Coin::new(15_000, "uusd")],
+            }),
+        ]
+    );
+    assert_eq!(
+        FINISHED_JOBS().load(&deps.storage, 1).unwrap().status,
+        JobStatus::Evicted
+    );
+}
+
+#[test]
+fn test_evict_job_reward_smaller_than_fee() {
+    let mut deps = controller_with(mock_config(), mock_job(1));
+
+    assert_eq!(
+        evict(&mut deps).unwrap_err(),
+        ContractError::RewardSmallerThanFee {}
+    );
+    assert!(PENDING_JOBS().has(&deps.storage, 1));
+}
+
+#[test]
+fn test_evict_job_not_eligible_at_height() {
+    let mut job = mock_job(1);
+    job.reward = Uint128::new(20_000);
+    job.eligible_at = Some(Expiration::AtHeight(mock_env().block.height + 1));
+    let mut deps = controller_with(mock_config(), job);
+
+    assert_eq!(
+        evict(&mut deps).unwrap_err(),
+        ContractError::EvictionPeriodNotElapsed {}
+    );
+}
+
+#[test]
+fn test_evict_job_requeue_reopens_exclusivity() {
+    let mut job = mock_job(1);
+    job.reward = Uint128::new(20_000);
+    job.requeue_on_evict = true;
+    job.exclusivity = Some(JobExclusivity {
+        keepers: vec![Addr::unchecked("keeper")],
+        window: Duration::Height(5),
+    });
+    job.exclusive_until = Some(Expiration::AtHeight(mock_env().block.height - 100));
+    let mut deps = controller_with(mock_config(), job);
+    deps.querier
+        .update_balance("account", vec![Coin::new(20_000, "uluna")]);
+
+    evict(&mut deps).unwrap();
+
+    let job = PENDING_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(job.status, JobStatus::Pending);
+    assert_eq!(
+        job.exclusive_until,
+        Some(Expiration::AtHeight(mock_env().block.height + 5))
+    );
+}
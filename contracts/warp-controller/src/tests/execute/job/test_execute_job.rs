@@ -1,3 +1,20 @@
+use crate::contract::{execute, query, reply};
+use crate::state::{ACCOUNTS, FINISHED_JOBS, PENDING_JOBS, STATE};
+use crate::tests::helpers::{mock_controller, mock_job, mock_resolver};
+use crate::ContractError;
+use controller::account::{Account, MsgExecutionSummary, MsgPolicy, MsgResult, MsgStatus};
+use controller::job::{
+    ExecuteJobMsg, JobBranch, JobExclusivity, JobFailureReason, JobResponse, JobStatus,
+    OutputBinding, OutputSource, QueryJobMsg, RetryPolicy,
+};
+use controller::{ExecuteMsg, QueryMsg, State};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, Binary, ContractResult, CosmosMsg, Event, Order, Reply,
+    SubMsgResponse, SubMsgResult, SystemResult, Uint64, WasmQuery,
+};
+use cw_utils::{Duration, Expiration};
+
 #[test]
 fn test_execute_job_successful() {}
 
@@ -20,3 +37,563 @@ fn test_execute_job_condition_improper_selector() {}
 
 #[test]
 fn test_execute_job_does_not_exist() {}
+
+#[test]
+fn test_execute_job_exclusive_window() {
+    let mut deps = mock_controller();
+    mock_resolver(&mut deps.querier, true);
+
+    for keeper in ["keeper", "other_keeper"] {
+        ACCOUNTS()
+            .save(
+                &mut deps.storage,
+                Addr::unchecked(keeper),
+                &Account {
+                    owner: Addr::unchecked(keeper),
+                    account: Addr::unchecked(format!("{}_account", keeper)),
+                },
+            )
+            .unwrap();
+    }
+
+    let mut job = mock_job(1);
+    job.exclusivity = Some(JobExclusivity {
+        keepers: vec![Addr::unchecked("keeper")],
+        window: Duration::Time(60),
+    });
+    job.exclusive_until = Some(Expiration::AtTime(mock_env().block.time.plus_seconds(60)));
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+
+    let execute_job_msg = ExecuteMsg::ExecuteJob(ExecuteJobMsg {
+        id: Uint64::one(),
+        external_inputs: None,
+    });
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("other_keeper", &[]),
+        execute_job_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ExclusiveExecutionWindow {});
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(60);
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("other_keeper", &[]),
+        execute_job_msg,
+    )
+    .unwrap();
+}
+
+fn recur(stable_id: bool) -> (State, u64) {
+    let mut deps = mock_controller();
+    mock_resolver(&mut deps.querier, true);
+    deps.querier
+        .update_balance("account", coins(100_000, "uluna"));
+
+    let mut job = mock_job(1);
+    job.recurring = true;
+    job.stable_id = stable_id;
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    let pending = PENDING_JOBS()
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+
+    let requeued = PENDING_JOBS().load(&deps.storage, pending[0]).unwrap();
+    assert_eq!(requeued.status, JobStatus::Pending);
+    assert_eq!(requeued.executions, Uint64::one());
+    assert_eq!(requeued.series_id, Uint64::one());
+
+    // only a job requeued under a new id leaves its run behind in the finished map
+    assert_eq!(FINISHED_JOBS().has(&deps.storage, 1), pending[0] != 1);
+
+    (STATE.load(&deps.storage).unwrap(), pending[0])
+}
+
+// runs the last allowed execution of a recurring job and returns the job as queried after it
+fn last_run(max_executions: Option<Uint64>, expires_at: Option<Expiration>) -> JobResponse {
+    let mut deps = mock_controller();
+    mock_resolver(&mut deps.querier, true);
+    deps.querier
+        .update_balance("account", coins(100_000, "uluna"));
+
+    let mut job = mock_job(1);
+    job.recurring = true;
+    job.max_executions = max_executions;
+    job.expires_at = expires_at;
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    assert!(PENDING_JOBS().is_empty(&deps.storage));
+
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QueryJob(QueryJobMsg { id: Uint64::one() }),
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_execute_job_recurring_max_executions_completes() {
+    let res = last_run(Some(Uint64::one()), None);
+
+    assert_eq!(res.job.status, JobStatus::Completed);
+    assert_eq!(res.job.executions, Uint64::one());
+}
+
+#[test]
+fn test_execute_job_recurring_expired() {
+    let res = last_run(None, Some(Expiration::AtHeight(1)));
+
+    assert_eq!(res.job.status, JobStatus::Expired);
+}
+
+#[test]
+fn test_execute_job_recurring_requeued_under_new_id() {
+    let (state, requeued_id) = recur(false);
+
+    assert_eq!(requeued_id, 2);
+    assert_eq!(state.current_job_id, Uint64::new(3));
+    assert_eq!(state.q, Uint64::one());
+}
+
+#[test]
+fn test_execute_job_recurring_stable_id() {
+    let (state, requeued_id) = recur(true);
+
+    assert_eq!(requeued_id, 1);
+    assert_eq!(state.current_job_id, Uint64::new(2));
+    assert_eq!(state.q, Uint64::one());
+}
+
+#[test]
+fn test_execute_job_condition_invalid_records_failure_reason() {
+    let mut deps = mock_controller();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => SystemResult::Ok(match from_binary(msg).unwrap() {
+            resolver::QueryMsg::QueryResolveCondition(_) => {
+                ContractResult::Err("Condition input invalid".to_string())
+            }
+            _ => ContractResult::Ok(to_binary(&"[]".to_string()).unwrap()),
+        }),
+        _ => panic!("unexpected query"),
+    });
+
+    PENDING_JOBS()
+        .save(&mut deps.storage, 1, &mock_job(1))
+        .unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+    ACCOUNTS()
+        .save(
+            &mut deps.storage,
+            Addr::unchecked("keeper"),
+            &Account {
+                owner: Addr::unchecked("keeper"),
+                account: Addr::unchecked("keeper_account"),
+            },
+        )
+        .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteJob(ExecuteJobMsg {
+            id: Uint64::one(),
+            external_inputs: None,
+        }),
+    )
+    .unwrap();
+
+    let job = FINISHED_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(job.status, JobStatus::Failed);
+    assert!(matches!(
+        job.failure_reason,
+        Some(JobFailureReason::ConditionInvalid { .. })
+    ));
+}
+
+#[test]
+fn test_execute_job_reply_records_failure_reason() {
+    let mut deps = mock_controller();
+    mock_resolver(&mut deps.querier, true);
+
+    PENDING_JOBS()
+        .save(&mut deps.storage, 1, &mock_job(1))
+        .unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Err("codespace: wasm, code: 5".to_string()),
+        },
+    )
+    .unwrap();
+
+    let job = FINISHED_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(job.status, JobStatus::Failed);
+    assert!(matches!(
+        job.failure_reason,
+        Some(JobFailureReason::SubmsgError { code: Some(5), .. })
+    ));
+}
+
+#[test]
+fn test_execute_job_reply_retries_failed_job() {
+    let mut deps = mock_controller();
+    mock_resolver(&mut deps.querier, true);
+    deps.querier
+        .update_balance("account", coins(100_000, "uluna"));
+
+    let mut job = mock_job(1);
+    job.retry_policy = Some(RetryPolicy {
+        max_retries: Uint64::new(1),
+        backoff: Duration::Time(60),
+    });
+    job.retries_left = Uint64::one();
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+
+    let failed = Reply {
+        id: 1,
+        result: SubMsgResult::Err("codespace: wasm, code: 5".to_string()),
+    };
+
+    reply(deps.as_mut(), mock_env(), failed.clone()).unwrap();
+
+    let retried = PENDING_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(retried.status, JobStatus::Pending);
+    assert_eq!(retried.retries_left, Uint64::zero());
+    assert_eq!(retried.executions, Uint64::zero());
+    assert_eq!(
+        retried.eligible_at,
+        Some(Expiration::AtTime(mock_env().block.time.plus_seconds(60)))
+    );
+    assert!(!FINISHED_JOBS().has(&deps.storage, 1));
+    assert_eq!(STATE.load(&deps.storage).unwrap().q, Uint64::one());
+
+    // out of retries
+    reply(deps.as_mut(), mock_env(), failed).unwrap();
+
+    assert!(!PENDING_JOBS().has(&deps.storage, 1));
+    assert_eq!(
+        FINISHED_JOBS().load(&deps.storage, 1).unwrap().status,
+        JobStatus::Failed
+    );
+    assert_eq!(STATE.load(&deps.storage).unwrap().q, Uint64::zero());
+}
+
+#[test]
+fn test_execute_job_reply_retries_do_not_use_up_max_executions() {
+    let mut deps = mock_controller();
+    mock_resolver(&mut deps.querier, true);
+    deps.querier
+        .update_balance("account", coins(100_000, "uluna"));
+
+    let mut job = mock_job(1);
+    job.recurring = true;
+    job.max_executions = Some(Uint64::new(2));
+    job.retry_policy = Some(RetryPolicy {
+        max_retries: Uint64::new(1),
+        backoff: Duration::Time(60),
+    });
+    job.retries_left = Uint64::one();
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Err("codespace: wasm, code: 5".to_string()),
+        },
+    )
+    .unwrap();
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    // one run so far, so the job recurs instead of completing
+    let finished = FINISHED_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(finished.status, JobStatus::Executed);
+    assert_eq!(finished.executions, Uint64::one());
+    assert_eq!(
+        PENDING_JOBS()
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_execute_job_reply_records_msg_results() {
+    let mut deps = mock_controller();
+    mock_resolver(&mut deps.querier, true);
+
+    let mut job = mock_job(1);
+    job.msg_policies = Some(vec![MsgPolicy::Optional]);
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+
+    let results = vec![MsgResult {
+        index: 0,
+        status: MsgStatus::Failed,
+        error: Some("optional failed".to_string()),
+    }];
+    let summary = to_binary(&MsgExecutionSummary {
+        results: results.clone(),
+    })
+    .unwrap();
+
+    // the account response data wrapped in a MsgExecuteContractResponse
+    let mut data = vec![0x0a, summary.len() as u8];
+    data.extend_from_slice(summary.as_slice());
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(data)),
+            }),
+        },
+    )
+    .unwrap();
+
+    let job = FINISHED_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(job.status, JobStatus::Executed);
+    assert_eq!(job.msg_results, Some(results));
+}
+
+#[test]
+fn test_execute_job_sends_selected_branch() {
+    let mut deps = mock_controller();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => SystemResult::Ok(match from_binary(msg).unwrap() {
+            resolver::QueryMsg::QueryHydrateVars(_) => {
+                ContractResult::Ok(to_binary(&"[]".to_string()).unwrap())
+            }
+            resolver::QueryMsg::QueryResolveBranch(_) => {
+                ContractResult::Ok(to_binary(&Some(1u32)).unwrap())
+            }
+            // hydrating anything but the stop loss msgs fails the job
+            resolver::QueryMsg::QueryHydrateMsgs(data) if data.msgs == "stop_loss_msgs" => {
+                ContractResult::Ok(to_binary(&Vec::<CosmosMsg>::new()).unwrap())
+            }
+            _ => ContractResult::Err("unexpected query".to_string()),
+        }),
+        _ => panic!("unexpected query"),
+    });
+
+    let mut job = mock_job(1);
+    job.branches = vec![JobBranch {
+        condition: "stop_loss".to_string(),
+        msgs: "stop_loss_msgs".to_string(),
+    }];
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+    ACCOUNTS()
+        .save(
+            &mut deps.storage,
+            Addr::unchecked("keeper"),
+            &Account {
+                owner: Addr::unchecked("keeper"),
+                account: Addr::unchecked("keeper_account"),
+            },
+        )
+        .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteJob(ExecuteJobMsg {
+            id: Uint64::one(),
+            external_inputs: None,
+        }),
+    )
+    .unwrap();
+
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "job_condition_status" && attr.value == "valid"));
+    assert!(PENDING_JOBS().has(&deps.storage, 1));
+}
+
+#[test]
+fn test_execute_job_reply_captures_outputs() {
+    let mut deps = mock_controller();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => SystemResult::Ok(match from_binary(msg).unwrap() {
+            resolver::QueryMsg::QueryCaptureOutputs(data) => {
+                let amount = &data.events[0].attributes[0].value;
+                ContractResult::Ok(to_binary(&format!("[\"{}\"]", amount)).unwrap())
+            }
+            _ => ContractResult::Err("unexpected query".to_string()),
+        }),
+        _ => panic!("unexpected query"),
+    });
+
+    let mut job = mock_job(1);
+    job.output_bindings = vec![OutputBinding {
+        var: "received".to_string(),
+        source: OutputSource::EventAttribute {
+            event_type: "wasm".to_string(),
+            key: "return_amount".to_string(),
+        },
+        selector: None,
+    }];
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![Event::new("wasm").add_attribute("return_amount", "1500")],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    let job = FINISHED_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(job.vars, "[\"1500\"]");
+}
@@ -0,0 +1,165 @@
+use crate::contract::execute;
+use crate::state::{PAUSED_JOBS, PENDING_JOBS, STATE};
+use crate::tests::helpers::{mock_controller, mock_job};
+use crate::ContractError;
+use controller::job::{
+    EvictJobMsg, ExecuteJobMsg, JobExclusivity, JobStatus, PauseJobMsg, ResumeJobMsg,
+};
+use controller::{ExecuteMsg, State};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{Addr, OwnedDeps, Timestamp, Uint64};
+use cw_utils::{Duration, Expiration};
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_controller();
+    let mut job = mock_job(1);
+    job.vars = "[\"var\"]".to_string();
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+    deps
+}
+
+#[test]
+fn test_pause_and_resume_job() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::PauseJob(PauseJobMsg { id: Uint64::one() }),
+    )
+    .unwrap();
+
+    assert!(!PENDING_JOBS().has(&deps.storage, 1));
+    assert_eq!(
+        PAUSED_JOBS().load(&deps.storage, 1).unwrap().status,
+        JobStatus::Paused
+    );
+    assert_eq!(STATE.load(&deps.storage).unwrap().q, Uint64::zero());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::ExecuteJob(ExecuteJobMsg {
+            id: Uint64::one(),
+            external_inputs: None,
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::JobPaused {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::ResumeJob(ResumeJobMsg { id: Uint64::one() }),
+    )
+    .unwrap();
+
+    let job = PENDING_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(job.status, JobStatus::Pending);
+    assert_eq!(job.vars, "[\"var\"]");
+    assert_eq!(
+        job.last_update_time,
+        Uint64::new(mock_env().block.time.seconds())
+    );
+    assert!(!PAUSED_JOBS().has(&deps.storage, 1));
+    assert_eq!(STATE.load(&deps.storage).unwrap().q, Uint64::one());
+}
+
+#[test]
+fn test_pause_job_unauthorized() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::PauseJob(PauseJobMsg { id: Uint64::one() }),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_resume_job_reopens_exclusivity() {
+    let mut deps = setup();
+    let mut job = PENDING_JOBS().load(&deps.storage, 1).unwrap();
+    job.exclusivity = Some(JobExclusivity {
+        keepers: vec![Addr::unchecked("keeper")],
+        window: Duration::Time(60),
+    });
+    // both closed while the job was paused
+    job.eligible_at = Some(Expiration::AtTime(mock_env().block.time.minus_seconds(600)));
+    job.exclusive_until = Some(Expiration::AtTime(mock_env().block.time.minus_seconds(540)));
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::PauseJob(PauseJobMsg { id: Uint64::one() }),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::ResumeJob(ResumeJobMsg { id: Uint64::one() }),
+    )
+    .unwrap();
+
+    let job = PENDING_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(job.eligible_at, None);
+    assert_eq!(
+        job.exclusive_until,
+        Some(Expiration::AtTime(mock_env().block.time.plus_seconds(60)))
+    );
+}
+
+#[test]
+fn test_evict_job_right_after_resume() {
+    let mut deps = setup();
+    let mut job = PENDING_JOBS().load(&deps.storage, 1).unwrap();
+    job.last_update_time = Uint64::zero();
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+
+    // paused long enough for the job to have aged past the eviction period
+    let mut paused_at = mock_env();
+    paused_at.block.time = Timestamp::from_seconds(1);
+    execute(
+        deps.as_mut(),
+        paused_at,
+        mock_info("owner", &[]),
+        ExecuteMsg::PauseJob(PauseJobMsg { id: Uint64::one() }),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::ResumeJob(ResumeJobMsg { id: Uint64::one() }),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("evictor", &[]),
+        ExecuteMsg::EvictJob(EvictJobMsg { id: Uint64::one() }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EvictionPeriodNotElapsed {});
+    assert!(PENDING_JOBS().has(&deps.storage, 1));
+}
@@ -0,0 +1,176 @@
+use crate::contract::execute;
+use crate::state::{CONFIG, FINISHED_JOBS, JOB_REVISIONS, JOB_SUMMARIES, KEEPERS};
+use crate::tests::helpers::{mock_controller, mock_job};
+use crate::util::dependency::dependencies_met;
+use crate::ContractError;
+use controller::job::{
+    DependencyStatus, Job, JobDependency, JobRevision, JobStatus, PruneFinishedJobsMsg,
+};
+use controller::keeper::Keeper;
+use controller::ExecuteMsg;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{coins, Addr, OwnedDeps, Uint128, Uint64};
+
+const RETENTION: u64 = 1_000;
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_controller();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.finished_job_retention = Some(Uint64::new(RETENTION));
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let now = mock_env().block.time.seconds();
+    for id in 1..=3u64 {
+        FINISHED_JOBS()
+            .save(
+                &mut deps.storage,
+                id,
+                &Job {
+                    status: JobStatus::Executed,
+                    // job 3 finished too recently to be pruned
+                    finished_at: Some(Uint64::new(if id == 3 { now } else { now - RETENTION })),
+                    else_msgs: (id == 2).then(|| "[]".to_string()),
+                    ..mock_job(id)
+                },
+            )
+            .unwrap();
+    }
+    JOB_REVISIONS
+        .save(
+            &mut deps.storage,
+            (1, 1),
+            &JobRevision {
+                revision: Uint64::one(),
+                updated_at: Uint64::zero(),
+                changes: vec![],
+            },
+        )
+        .unwrap();
+
+    deps
+}
+
+fn prune(limit: u32) -> ExecuteMsg {
+    ExecuteMsg::PruneFinishedJobs(PruneFinishedJobsMsg {
+        start_after: None,
+        limit,
+    })
+}
+
+#[test]
+fn test_prune_finished_jobs() {
+    let mut deps = setup();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        prune(10),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    assert!(!FINISHED_JOBS().has(&deps.storage, 1));
+    assert!(!FINISHED_JOBS().has(&deps.storage, 2));
+    assert!(FINISHED_JOBS().has(&deps.storage, 3));
+    assert!(!JOB_REVISIONS.has(&deps.storage, (1, 1)));
+
+    let summary = JOB_SUMMARIES.load(&deps.storage, 1).unwrap();
+    assert_eq!(summary.status, JobStatus::Executed);
+    assert_eq!(summary.reward, mock_job(1).reward);
+    assert_eq!(summary.owner, Addr::unchecked("owner"));
+    assert_eq!(summary.branches_hash, None);
+    // branch msgs are covered like the job's own msgs
+    assert!(JOB_SUMMARIES
+        .load(&deps.storage, 2)
+        .unwrap()
+        .branches_hash
+        .is_some());
+    assert!(!JOB_SUMMARIES.has(&deps.storage, 3));
+
+    // jobs depending on a pruned job still see its final status
+    let dependent = Job {
+        dependencies: vec![JobDependency {
+            job_id: Uint64::one(),
+            required_status: DependencyStatus::Executed,
+        }],
+        ..mock_job(4)
+    };
+    assert!(dependencies_met(&deps.storage, &dependent).unwrap());
+}
+
+#[test]
+fn test_prune_finished_jobs_authorization() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        prune(10),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), prune(0)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidBatchSize { max: 50 });
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.finished_job_retention = None;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        prune(10),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PruningDisabled {});
+}
+
+#[test]
+fn test_prune_finished_jobs_keeper_bounty() {
+    let mut deps = setup();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.prune_bounty = Uint128::new(100);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    KEEPERS()
+        .save(
+            &mut deps.storage,
+            Addr::unchecked("keeper"),
+            &Keeper {
+                address: Addr::unchecked("keeper"),
+                stake: Uint128::zero(),
+                unbonding: Uint128::zero(),
+                unbonding_release_at: None,
+                executed: Uint64::zero(),
+                failed: Uint64::zero(),
+                evicted: Uint64::zero(),
+                slashed: Uint128::zero(),
+                rewards_earned: vec![],
+            },
+        )
+        .unwrap();
+    // the owner's account only covers the bounty of one of the two jobs
+    deps.querier.update_balance("account", coins(150, "uluna"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        prune(10),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "prune_bounty" && a.value == "100"));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "last_job_id" && a.value == "3"));
+}
@@ -1,3 +1,33 @@
+use crate::contract::{execute, query};
+use crate::state::PENDING_JOBS;
+use crate::tests::helpers::{mock_controller, mock_job};
+use crate::util::branch::MAX_BRANCHES;
+use crate::ContractError;
+use controller::job::{
+    JobBranch, JobFieldChange, JobRevisionsResponse, QueryJobRevisionsMsg, UpdateJobMsg,
+};
+use controller::{ExecuteMsg, QueryMsg};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, Uint64};
+
+fn update_job_msg(id: u64) -> UpdateJobMsg {
+    UpdateJobMsg {
+        id: Uint64::new(id),
+        name: None,
+        description: None,
+        labels: None,
+        added_reward: None,
+        condition: None,
+        terminate_condition: None,
+        msgs: None,
+        vars: None,
+        recurring: None,
+        requeue_on_evict: None,
+        branches: None,
+        else_msgs: None,
+    }
+}
+
 #[test]
 fn test_update_job_successful() {}
 
@@ -9,3 +39,190 @@ fn test_update_job_not_pending() {}
 
 #[test]
 fn test_update_job_add_too_little_reward() {}
+
+#[test]
+fn test_update_job_condition_and_msgs_records_revision() {
+    let mut deps = mock_controller();
+    PENDING_JOBS()
+        .save(&mut deps.storage, 1, &mock_job(1))
+        .unwrap();
+
+    let mut msg = update_job_msg(1);
+    msg.condition = Some("{\"expr\":\"new\"}".to_string());
+    msg.msgs = Some("[\"msg\"]".to_string());
+    msg.recurring = Some(false);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateJob(msg),
+    )
+    .unwrap();
+
+    let job = PENDING_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(job.condition, "{\"expr\":\"new\"}");
+    assert_eq!(job.msgs, "[\"msg\"]");
+
+    let mut msg = update_job_msg(1);
+    msg.requeue_on_evict = Some(true);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateJob(msg),
+    )
+    .unwrap();
+
+    let res: JobRevisionsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QueryJobRevisions(QueryJobRevisionsMsg {
+                id: Uint64::new(1),
+                start_after: None,
+                limit: None,
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(res.revisions.len(), 2);
+    assert_eq!(res.revisions[0].revision, Uint64::new(1));
+    // unchanged recurring flag is not recorded
+    assert_eq!(
+        res.revisions[0].changes,
+        vec![
+            JobFieldChange {
+                field: "condition".to_string(),
+                old_value: "\"\"".to_string(),
+                new_value: "\"{\\\"expr\\\":\\\"new\\\"}\"".to_string(),
+            },
+            JobFieldChange {
+                field: "msgs".to_string(),
+                old_value: "\"[]\"".to_string(),
+                new_value: "\"[\\\"msg\\\"]\"".to_string(),
+            },
+        ]
+    );
+    assert_eq!(res.revisions[1].revision, Uint64::new(2));
+    assert_eq!(res.revisions[1].changes[0].field, "requeue_on_evict");
+}
+
+fn branch(name: &str) -> JobBranch {
+    JobBranch {
+        condition: format!("{}_condition", name),
+        msgs: format!("{}_msgs", name),
+    }
+}
+
+#[test]
+fn test_update_job_branches_and_else_msgs() {
+    let mut deps = mock_controller();
+    PENDING_JOBS()
+        .save(&mut deps.storage, 1, &mock_job(1))
+        .unwrap();
+
+    let mut msg = update_job_msg(1);
+    msg.branches = Some(vec![branch("stop_loss")]);
+    msg.else_msgs = Some("else_msgs".to_string());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateJob(msg),
+    )
+    .unwrap();
+
+    let job = PENDING_JOBS().load(&deps.storage, 1).unwrap();
+    assert_eq!(job.branches, vec![branch("stop_loss")]);
+    assert_eq!(job.else_msgs, Some("else_msgs".to_string()));
+
+    let res: JobRevisionsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QueryJobRevisions(QueryJobRevisionsMsg {
+                id: Uint64::new(1),
+                start_after: None,
+                limit: None,
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let fields = res.revisions[0]
+        .changes
+        .iter()
+        .map(|change| change.field.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, vec!["branches", "else_msgs"]);
+
+    // an empty list removes the branches
+    let mut msg = update_job_msg(1);
+    msg.branches = Some(vec![]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateJob(msg),
+    )
+    .unwrap();
+    assert!(PENDING_JOBS()
+        .load(&deps.storage, 1)
+        .unwrap()
+        .branches
+        .is_empty());
+}
+
+#[test]
+fn test_update_job_too_many_branches() {
+    let mut deps = mock_controller();
+    PENDING_JOBS()
+        .save(&mut deps.storage, 1, &mock_job(1))
+        .unwrap();
+
+    let mut msg = update_job_msg(1);
+    msg.branches = Some(vec![branch("extra"); MAX_BRANCHES + 1]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateJob(msg),
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, ContractError::InvalidBranches { .. }));
+    assert!(PENDING_JOBS()
+        .load(&deps.storage, 1)
+        .unwrap()
+        .branches
+        .is_empty());
+}
+
+#[test]
+fn test_update_job_stable_id_not_recurring() {
+    let mut deps = mock_controller();
+    let mut job = mock_job(1);
+    job.recurring = true;
+    job.stable_id = true;
+    PENDING_JOBS().save(&mut deps.storage, 1, &job).unwrap();
+
+    let mut msg = update_job_msg(1);
+    msg.recurring = Some(false);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateJob(msg),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::StableIdNotRecurring {});
+    assert!(PENDING_JOBS().load(&deps.storage, 1).unwrap().recurring);
+}
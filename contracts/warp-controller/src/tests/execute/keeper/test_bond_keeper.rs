@@ -0,0 +1,412 @@
+use crate::contract::execute;
+use crate::state::{ACCOUNTS, CONFIG, KEEPERS, PENDING_JOBS, STATE};
+use crate::tests::helpers::{mock_controller, mock_job, mock_resolver};
+use crate::ContractError;
+use controller::account::Account;
+use controller::job::ExecuteJobMsg;
+use controller::keeper::{BondKeeperMsg, UnbondKeeperMsg, WithdrawKeeperStakeMsg};
+use controller::{ExecuteMsg, State};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, ContractResult, CosmosMsg, OwnedDeps, Response,
+    SystemResult, Uint128, Uint64, WasmQuery,
+};
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_controller();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.keeper_min_stake = Uint128::new(1_000);
+    config.keeper_slash_percentage = Uint64::new(10);
+    config.keeper_unbonding_period = Uint64::new(100);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    ACCOUNTS()
+        .save(
+            &mut deps.storage,
+            Addr::unchecked("keeper"),
+            &Account {
+                owner: Addr::unchecked("keeper"),
+                account: Addr::unchecked("keeper_account"),
+            },
+        )
+        .unwrap();
+
+    PENDING_JOBS()
+        .save(&mut deps.storage, 1, &mock_job(1))
+        .unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::new(2),
+                q: Uint64::one(),
+            },
+        )
+        .unwrap();
+
+    deps
+}
+
+fn execute_job_msg() -> ExecuteMsg {
+    ExecuteMsg::ExecuteJob(ExecuteJobMsg {
+        id: Uint64::one(),
+        external_inputs: None,
+    })
+}
+
+#[test]
+fn test_bond_and_unbond_keeper() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &coins(1_000, "uluna")),
+        ExecuteMsg::BondKeeper(BondKeeperMsg {}),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &coins(500, "uluna")),
+        ExecuteMsg::BondKeeper(BondKeeperMsg {}),
+    )
+    .unwrap();
+
+    let keeper = KEEPERS()
+        .load(&deps.storage, Addr::unchecked("keeper"))
+        .unwrap();
+    assert_eq!(keeper.stake, Uint128::new(1_500));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::UnbondKeeper(UnbondKeeperMsg {
+            amount: Some(Uint128::new(700)),
+        }),
+    )
+    .unwrap();
+
+    // unbonded stake is queued, not sent
+    assert!(res.messages.is_empty());
+    let keeper = KEEPERS()
+        .load(&deps.storage, Addr::unchecked("keeper"))
+        .unwrap();
+    assert_eq!(keeper.stake, Uint128::new(800));
+    assert_eq!(keeper.unbonding, Uint128::new(700));
+
+    // below the minimum stake the keeper can no longer execute
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        execute_job_msg(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientKeeperStake {});
+}
+
+#[test]
+fn test_withdraw_keeper_stake_after_unbonding_period() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &coins(1_000, "uluna")),
+        ExecuteMsg::BondKeeper(BondKeeperMsg {}),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::UnbondKeeper(UnbondKeeperMsg { amount: None }),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::WithdrawKeeperStake(WithdrawKeeperStakeMsg {}),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::KeeperUnbondingNotReleased {});
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("keeper", &[]),
+        ExecuteMsg::WithdrawKeeperStake(WithdrawKeeperStakeMsg {}),
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "keeper".to_string(),
+            amount: coins(1_000, "uluna"),
+        })
+    );
+    let keeper = KEEPERS()
+        .load(&deps.storage, Addr::unchecked("keeper"))
+        .unwrap();
+    assert_eq!(keeper.unbonding, Uint128::zero());
+    assert_eq!(keeper.unbonding_release_at, None);
+}
+
+#[test]
+fn test_unbond_keeper_zero_amount() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &coins(1_000, "uluna")),
+        ExecuteMsg::BondKeeper(BondKeeperMsg {}),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::UnbondKeeper(UnbondKeeperMsg {
+            amount: Some(Uint128::zero()),
+        }),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::InvalidKeeperUnbondAmount {});
+}
+
+#[test]
+fn test_bond_keeper_wrong_denom() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &coins(1_000, "uusd")),
+        ExecuteMsg::BondKeeper(BondKeeperMsg {}),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::InvalidKeeperStake {});
+}
+
+#[test]
+fn test_unregistered_keeper_rejected() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        execute_job_msg(),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::KeeperNotRegistered {});
+}
+
+#[test]
+fn test_unregistered_keeper_rejected_without_min_stake_when_slashing() {
+    let mut deps = setup();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.keeper_min_stake = Uint128::zero();
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        execute_job_msg(),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::KeeperNotRegistered {});
+}
+
+#[test]
+fn test_keeper_slashed_on_false_condition() {
+    let mut deps = setup();
+    mock_resolver(&mut deps.querier, false);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &coins(2_000, "uluna")),
+        ExecuteMsg::BondKeeper(BondKeeperMsg {}),
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        execute_job_msg(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "fee_collector".to_string(),
+            amount: coins(200, "uluna"),
+        })
+    );
+
+    let keeper = KEEPERS()
+        .load(&deps.storage, Addr::unchecked("keeper"))
+        .unwrap();
+    assert_eq!(keeper.stake, Uint128::new(1_800));
+    assert_eq!(keeper.slashed, Uint128::new(200));
+    assert!(PENDING_JOBS().has(&deps.storage, 1));
+}
+
+#[test]
+fn test_keeper_rewarded_on_execution() {
+    let mut deps = setup();
+    mock_resolver(&mut deps.querier, true);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &coins(2_000, "uluna")),
+        ExecuteMsg::BondKeeper(BondKeeperMsg {}),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        execute_job_msg(),
+    )
+    .unwrap();
+
+    let keeper = KEEPERS()
+        .load(&deps.storage, Addr::unchecked("keeper"))
+        .unwrap();
+    assert_eq!(keeper.rewards_earned[0].amount, Uint128::new(1_000));
+    assert_eq!(keeper.stake, Uint128::new(2_000));
+}
+
+// resolver failing either the condition or the var hydration
+fn mock_failing_resolver(querier: &mut MockQuerier, condition_fails: bool) {
+    querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { msg, .. } => SystemResult::Ok(match from_binary(msg).unwrap() {
+            resolver::QueryMsg::QueryHydrateVars(_) if condition_fails => {
+                ContractResult::Ok(to_binary(&"[]".to_string()).unwrap())
+            }
+            resolver::QueryMsg::QueryHydrateVars(_) => ContractResult::Err("bad var".to_string()),
+            _ => ContractResult::Err("bad condition".to_string()),
+        }),
+        _ => panic!("unexpected query"),
+    });
+}
+
+fn bond_and_execute(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &coins(2_000, "uluna")),
+        ExecuteMsg::BondKeeper(BondKeeperMsg {}),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        execute_job_msg(),
+    )
+}
+
+#[test]
+fn test_keeper_slashed_on_invalid_condition_earns_no_reward() {
+    let mut deps = setup();
+    mock_failing_resolver(&mut deps.querier, true);
+
+    let res = bond_and_execute(&mut deps).unwrap();
+
+    // the whole reward goes back to the owner
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|msg| msg.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "account".to_string(),
+                amount: coins(1_000, "uluna"),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "fee_collector".to_string(),
+                amount: coins(200, "uluna"),
+            }),
+        ]
+    );
+
+    let keeper = KEEPERS()
+        .load(&deps.storage, Addr::unchecked("keeper"))
+        .unwrap();
+    assert_eq!(keeper.stake, Uint128::new(1_800));
+    assert_eq!(keeper.failed, Uint64::one());
+    assert!(keeper.rewards_earned.is_empty());
+}
+
+#[test]
+fn test_keeper_not_slashed_on_hydration_error() {
+    let mut deps = setup();
+    mock_failing_resolver(&mut deps.querier, false);
+
+    let res = bond_and_execute(&mut deps).unwrap();
+
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "keeper_account".to_string(),
+            amount: coins(1_000, "uluna"),
+        })
+    );
+    assert!(!res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "keeper_slashed"));
+
+    let keeper = KEEPERS()
+        .load(&deps.storage, Addr::unchecked("keeper"))
+        .unwrap();
+    assert_eq!(keeper.stake, Uint128::new(2_000));
+    assert_eq!(keeper.failed, Uint64::one());
+    assert_eq!(keeper.rewards_earned[0].amount, Uint128::new(1_000));
+}
+
+#[test]
+fn test_false_condition_rejected_without_slashing() {
+    let mut deps = setup();
+    mock_resolver(&mut deps.querier, false);
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.keeper_slash_percentage = Uint64::zero();
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    // a registered keeper is turned away like an unregistered caller
+    let err = bond_and_execute(&mut deps).unwrap_err();
+    assert_eq!(err, ContractError::JobNotActive {});
+
+    let keeper = KEEPERS()
+        .load(&deps.storage, Addr::unchecked("keeper"))
+        .unwrap();
+    assert_eq!(keeper.stake, Uint128::new(2_000));
+}
@@ -1,4 +1,5 @@
 mod account;
 mod controller;
 mod job;
+mod keeper;
 mod template;
@@ -1 +1,161 @@
+use crate::state::{ACCOUNTS, CONFIG, STATE};
+use controller::account::Account;
+use controller::job::{CreateJobMsg, Job, JobStatus};
+use controller::{Config, State};
+use cosmwasm_std::testing::{mock_dependencies, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, ContractResult, CosmosMsg, OwnedDeps, SystemResult, Uint128,
+    Uint64, WasmQuery,
+};
 
+pub fn mock_job(id: u64) -> Job {
+    Job {
+        id: Uint64::new(id),
+        owner: Addr::unchecked("owner"),
+        last_update_time: Uint64::zero(),
+        name: format!("job_{}", id),
+        description: "".to_string(),
+        labels: vec![],
+        status: JobStatus::Pending,
+        condition: "".to_string(),
+        terminate_condition: None,
+        msgs: "[]".to_string(),
+        vars: "[]".to_string(),
+        recurring: false,
+        requeue_on_evict: false,
+        reward: Uint128::new(1_000),
+        assets_to_withdraw: vec![],
+        schedule: None,
+        eligible_at: None,
+        max_executions: None,
+        expires_at: None,
+        executions: Uint64::zero(),
+        reward_asset: None,
+        dependencies: vec![],
+        gas_limit: None,
+        exclusivity: None,
+        exclusive_until: None,
+        reward_curve: None,
+        gas_allowance: None,
+        finished_at: None,
+        series_id: Uint64::new(id),
+        stable_id: false,
+        failure_reason: None,
+        retry_policy: None,
+        retries_left: Uint64::zero(),
+        msg_policies: None,
+        msg_results: None,
+        branches: vec![],
+        else_msgs: None,
+        output_bindings: vec![],
+    }
+}
+
+pub fn mock_create_job_msg(name: &str, reward: u128) -> CreateJobMsg {
+    CreateJobMsg {
+        name: name.to_string(),
+        description: "".to_string(),
+        labels: vec![],
+        condition: "".to_string(),
+        terminate_condition: None,
+        msgs: "[]".to_string(),
+        vars: "[]".to_string(),
+        recurring: false,
+        requeue_on_evict: false,
+        reward: Uint128::new(reward),
+        assets_to_withdraw: None,
+        schedule: None,
+        max_executions: None,
+        expires_at: None,
+        reward_asset: None,
+        dependencies: None,
+        gas_limit: None,
+        exclusivity: None,
+        reward_curve: None,
+        gas_allowance: None,
+        stable_id: None,
+        retry_policy: None,
+        msg_policies: None,
+        branches: None,
+        else_msgs: None,
+        output_bindings: None,
+    }
+}
+
+pub fn mock_config() -> Config {
+    Config {
+        owner: Addr::unchecked("owner"),
+        fee_denom: "uluna".to_string(),
+        fee_collector: Addr::unchecked("fee_collector"),
+        warp_account_code_id: Uint64::new(1),
+        minimum_reward: Uint128::new(10_000),
+        creation_fee_percentage: Uint64::new(5),
+        cancellation_fee_percentage: Uint64::new(5),
+        resolver_address: Addr::unchecked("resolver"),
+        t_max: Uint64::new(86_400),
+        t_min: Uint64::new(86_400),
+        a_max: Uint128::new(10_000),
+        a_min: Uint128::new(10_000),
+        q_max: Uint64::new(10),
+        reward_assets: vec![],
+        max_job_gas_limit: None,
+        keeper_min_stake: Uint128::zero(),
+        keeper_slash_percentage: Uint64::zero(),
+        keeper_unbonding_period: Uint64::zero(),
+        finished_job_retention: None,
+        prune_bounty: Uint128::zero(),
+    }
+}
+
+// controller with config, an empty queue and an account for "owner",
+// resolver queries always succeed
+pub fn mock_controller() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+
+    CONFIG.save(&mut deps.storage, &mock_config()).unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                current_job_id: Uint64::one(),
+                q: Uint64::zero(),
+            },
+        )
+        .unwrap();
+    ACCOUNTS()
+        .save(
+            &mut deps.storage,
+            Addr::unchecked("owner"),
+            &Account {
+                owner: Addr::unchecked("owner"),
+                account: Addr::unchecked("account"),
+            },
+        )
+        .unwrap();
+
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { .. } => {
+            SystemResult::Ok(ContractResult::Ok(to_binary(&None::<String>).unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    deps
+}
+
+// resolver answering the queries execute_job and reply make, with a fixed condition result
+pub fn mock_resolver(querier: &mut MockQuerier, condition: bool) {
+    querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { msg, .. } => {
+            let res = match from_binary(msg).unwrap() {
+                resolver::QueryMsg::QueryHydrateVars(_) => to_binary(&"[]".to_string()),
+                resolver::QueryMsg::QueryResolveCondition(_) => to_binary(&condition),
+                resolver::QueryMsg::QueryHydrateMsgs(_) => to_binary(&Vec::<CosmosMsg>::new()),
+                resolver::QueryMsg::QueryApplyVarFn(_) => to_binary(&"[]".to_string()),
+                _ => to_binary(&None::<String>),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        }
+        _ => panic!("unexpected query"),
+    });
+}
@@ -1,2 +1,3 @@
 mod test_query_job;
 mod test_query_jobs;
+mod test_simulate_job;
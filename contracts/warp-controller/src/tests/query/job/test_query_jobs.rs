@@ -1,3 +1,12 @@
+use crate::query::job::query_jobs;
+use crate::state::{FINISHED_JOBS, PENDING_JOBS};
+use crate::tests::helpers::{mock_controller, mock_job, mock_resolver};
+use controller::job::{
+    Job, JobCursor, JobSortKey, JobStatus, JobsResponse, LabelFilter, QueryJobsMsg, SortOrder,
+};
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Addr, Uint128, Uint64};
+
 #[test]
 fn test_query_jobs_successful_under_50() {}
 
@@ -19,3 +28,300 @@ fn test_query_jobs_limit_over_50() {
 fn test_query_jobs_by_id_limit_over_50() {
     //should fail out here
 }
+
+fn filters() -> QueryJobsMsg {
+    QueryJobsMsg {
+        ids: None,
+        active: None,
+        owner: None,
+        name: None,
+        name_prefix: None,
+        labels: None,
+        job_status: None,
+        condition_status: None,
+        recurring: None,
+        min_reward: None,
+        max_reward: None,
+        updated_after: None,
+        updated_before: None,
+        sort_by: None,
+        order: None,
+        start_after: None,
+        limit: None,
+    }
+}
+
+fn job_ids(res: JobsResponse) -> Vec<u64> {
+    res.jobs.iter().map(|job| job.id.u64()).collect()
+}
+
+#[test]
+fn test_query_jobs_combined_filters() {
+    let mut deps = mock_controller();
+
+    for id in 1..=6u64 {
+        let mut job = mock_job(id);
+        job.reward = Uint128::new(1_000 * id as u128);
+        job.last_update_time = Uint64::new(100 * id);
+        job.recurring = id % 2 == 0;
+        job.labels = match id {
+            1 | 2 => vec!["defi".to_string()],
+            3 | 4 => vec!["defi".to_string(), "dca".to_string()],
+            _ => vec!["nft".to_string()],
+        };
+        if id == 6 {
+            job.name = "swap_6".to_string();
+        }
+        PENDING_JOBS().save(&mut deps.storage, id, &job).unwrap();
+    }
+
+    let query = |msg: QueryJobsMsg| job_ids(query_jobs(deps.as_ref(), mock_env(), msg).unwrap());
+
+    assert_eq!(
+        query(QueryJobsMsg {
+            labels: Some(LabelFilter::All(vec![
+                "defi".to_string(),
+                "dca".to_string()
+            ])),
+            ..filters()
+        }),
+        vec![4, 3]
+    );
+    assert_eq!(
+        query(QueryJobsMsg {
+            labels: Some(LabelFilter::Any(vec!["dca".to_string(), "nft".to_string()])),
+            recurring: Some(true),
+            ..filters()
+        }),
+        vec![6, 4]
+    );
+    assert_eq!(
+        query(QueryJobsMsg {
+            min_reward: Some(Uint128::new(2_000)),
+            max_reward: Some(Uint128::new(4_000)),
+            updated_after: Some(Uint64::new(300)),
+            ..filters()
+        }),
+        vec![4, 3]
+    );
+    assert_eq!(
+        query(QueryJobsMsg {
+            name_prefix: Some("job_".to_string()),
+            min_reward: Some(Uint128::new(5_000)),
+            ..filters()
+        }),
+        vec![5]
+    );
+    assert_eq!(
+        query(QueryJobsMsg {
+            ids: Some(vec![Uint64::new(1), Uint64::new(2), Uint64::new(5)]),
+            labels: Some(LabelFilter::Any(vec!["defi".to_string()])),
+            owner: Some(Addr::unchecked("owner")),
+            ..filters()
+        }),
+        vec![2, 1]
+    );
+    assert_eq!(
+        query(QueryJobsMsg {
+            max_reward: Some(Uint128::new(5_000)),
+            start_after: Some(JobCursor {
+                value: Uint128::new(3_000),
+                id: Uint64::new(3),
+            }),
+            limit: Some(1),
+            ..filters()
+        }),
+        vec![2]
+    );
+
+    assert!(query_jobs(
+        deps.as_ref(),
+        mock_env(),
+        QueryJobsMsg {
+            min_reward: Some(Uint128::new(2)),
+            max_reward: Some(Uint128::new(1)),
+            ..filters()
+        }
+    )
+    .is_err());
+}
+
+#[test]
+fn test_query_jobs_condition_status() {
+    let mut deps = mock_controller();
+    PENDING_JOBS()
+        .save(&mut deps.storage, 1, &mock_job(1))
+        .unwrap();
+
+    mock_resolver(&mut deps.querier, true);
+    let res = query_jobs(
+        deps.as_ref(),
+        mock_env(),
+        QueryJobsMsg {
+            condition_status: Some(true),
+            ..filters()
+        },
+    )
+    .unwrap();
+    assert_eq!(job_ids(res), vec![1]);
+
+    mock_resolver(&mut deps.querier, false);
+    let res = query_jobs(
+        deps.as_ref(),
+        mock_env(),
+        QueryJobsMsg {
+            condition_status: Some(true),
+            ..filters()
+        },
+    )
+    .unwrap();
+    assert!(res.jobs.is_empty());
+}
+
+#[test]
+fn test_query_jobs_cursor_pagination() {
+    let mut deps = mock_controller();
+
+    for id in 1..=7u64 {
+        let mut job = mock_job(id);
+        // rewards out of id order, with a tie between 2 and 5
+        job.reward = Uint128::new([0, 300, 500, 100, 700, 500, 200, 600][id as usize]);
+        job.last_update_time = Uint64::new(1_000 - 10 * id);
+        PENDING_JOBS().save(&mut deps.storage, id, &job).unwrap();
+    }
+
+    let walk = |sort_by: JobSortKey, order: SortOrder| -> Vec<u64> {
+        let mut ids = vec![];
+        let mut start_after = None;
+        loop {
+            let res = query_jobs(
+                deps.as_ref(),
+                mock_env(),
+                QueryJobsMsg {
+                    sort_by: Some(sort_by.clone()),
+                    order: Some(order.clone()),
+                    start_after,
+                    limit: Some(3),
+                    ..filters()
+                },
+            )
+            .unwrap();
+            assert_eq!(res.total_count, Some(7));
+            ids.extend(res.jobs.iter().map(|job| job.id.u64()));
+            match res.next_cursor {
+                None => return ids,
+                next_cursor => start_after = next_cursor,
+            }
+        }
+    };
+
+    assert_eq!(
+        walk(JobSortKey::Reward, SortOrder::Desc),
+        vec![4, 7, 5, 2, 1, 6, 3]
+    );
+    assert_eq!(
+        walk(JobSortKey::Reward, SortOrder::Asc),
+        vec![3, 6, 1, 2, 5, 7, 4]
+    );
+    assert_eq!(
+        walk(JobSortKey::Id, SortOrder::Asc),
+        vec![1, 2, 3, 4, 5, 6, 7]
+    );
+    assert_eq!(
+        walk(JobSortKey::UpdateTime, SortOrder::Desc),
+        vec![1, 2, 3, 4, 5, 6, 7]
+    );
+    assert_eq!(
+        walk(JobSortKey::UpdateTime, SortOrder::Asc),
+        vec![7, 6, 5, 4, 3, 2, 1]
+    );
+
+    let res = query_jobs(
+        deps.as_ref(),
+        mock_env(),
+        QueryJobsMsg {
+            ids: Some((1..=7).map(Uint64::new).collect()),
+            sort_by: Some(JobSortKey::Id),
+            order: Some(SortOrder::Desc),
+            start_after: Some(JobCursor {
+                value: Uint128::new(6),
+                id: Uint64::new(6),
+            }),
+            limit: Some(2),
+            ..filters()
+        },
+    )
+    .unwrap();
+    assert_eq!(job_ids(res.clone()), vec![5, 4]);
+    assert_eq!(res.total_count, Some(7));
+    assert_eq!(
+        res.next_cursor,
+        Some(JobCursor {
+            value: Uint128::new(4),
+            id: Uint64::new(4),
+        })
+    );
+}
+
+#[test]
+fn test_query_jobs_total_count() {
+    let mut deps = mock_controller();
+
+    for id in 1..=5u64 {
+        let mut job = mock_job(id);
+        job.reward = Uint128::new(1_000 * id as u128);
+        if id == 5 {
+            job.owner = Addr::unchecked("other");
+        }
+        PENDING_JOBS().save(&mut deps.storage, id, &job).unwrap();
+    }
+
+    // a job moving to the finished map is counted there instead
+    PENDING_JOBS().remove(&mut deps.storage, 1).unwrap();
+    FINISHED_JOBS()
+        .save(
+            &mut deps.storage,
+            1,
+            &Job {
+                status: JobStatus::Executed,
+                ..mock_job(1)
+            },
+        )
+        .unwrap();
+
+    let count = |msg: QueryJobsMsg| {
+        query_jobs(deps.as_ref(), mock_env(), msg)
+            .unwrap()
+            .total_count
+    };
+
+    assert_eq!(
+        count(QueryJobsMsg {
+            limit: Some(1),
+            ..filters()
+        }),
+        Some(4)
+    );
+    assert_eq!(
+        count(QueryJobsMsg {
+            owner: Some(Addr::unchecked("owner")),
+            ..filters()
+        }),
+        Some(3)
+    );
+    assert_eq!(
+        count(QueryJobsMsg {
+            job_status: Some(JobStatus::Executed),
+            ..filters()
+        }),
+        Some(1)
+    );
+    assert_eq!(
+        count(QueryJobsMsg {
+            min_reward: Some(Uint128::new(3_000)),
+            limit: Some(1),
+            ..filters()
+        }),
+        None
+    );
+}
@@ -0,0 +1,195 @@
+use crate::contract::query;
+use crate::tests::helpers::{mock_controller, mock_create_job_msg};
+use controller::account::AssetInfo;
+use controller::job::{CreateJobMsg, GasAllowance, JobBranch, SimulateJobMsg, SimulateJobResponse};
+use controller::QueryMsg;
+use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_binary, to_binary, BankMsg, ContractResult, CosmosMsg, Decimal, OwnedDeps, StdResult,
+    SystemResult, Uint128, WasmQuery,
+};
+use resolver::condition::{ConditionTrace, ExprTrace};
+
+const CONDITION: &str = r#"{"or":[{"expr":{"timestamp":{"comparator":"0","op":"gt"}}},{"not":{"expr":{"block_height":{"comparator":"0","op":"gt"}}}}]}"#;
+
+fn expr_trace(result: bool) -> ConditionTrace {
+    ConditionTrace::Expr(ExprTrace {
+        left: "1".to_string(),
+        op: "gt".to_string(),
+        right: "0".to_string(),
+        result,
+    })
+}
+
+fn hydrated_msg() -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: "recipient".to_string(),
+        amount: vec![],
+    })
+}
+
+// the trace and the branch resolution come from the given results, hydration always succeeds
+fn mock_simulation(
+    trace: Result<ConditionTrace, String>,
+    resolution: Result<bool, String>,
+) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_controller();
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { msg, .. } => SystemResult::Ok(match from_binary(msg).unwrap() {
+            resolver::QueryMsg::QueryHydrateVars(_) => {
+                ContractResult::Ok(to_binary(&"[]".to_string()).unwrap())
+            }
+            resolver::QueryMsg::QueryResolveConditionTrace(_) => match &trace {
+                Ok(trace) => ContractResult::Ok(to_binary(trace).unwrap()),
+                Err(e) => ContractResult::Err(e.clone()),
+            },
+            resolver::QueryMsg::QueryResolveCondition(_) => match &resolution {
+                Ok(resolution) => ContractResult::Ok(to_binary(resolution).unwrap()),
+                Err(e) => ContractResult::Err(e.clone()),
+            },
+            resolver::QueryMsg::QueryResolveBranch(_) => match &resolution {
+                Ok(resolution) => {
+                    ContractResult::Ok(to_binary(&resolution.then_some(0u32)).unwrap())
+                }
+                Err(e) => ContractResult::Err(e.clone()),
+            },
+            resolver::QueryMsg::QueryHydrateMsgs(_) => {
+                ContractResult::Ok(to_binary(&vec![hydrated_msg()]).unwrap())
+            }
+            _ => ContractResult::Ok(to_binary(&None::<String>).unwrap()),
+        }),
+        _ => panic!("unexpected query"),
+    });
+    deps
+}
+
+fn simulate(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    job: CreateJobMsg,
+) -> StdResult<SimulateJobResponse> {
+    from_binary(&query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateJob(SimulateJobMsg {
+            job,
+            external_inputs: None,
+        }),
+    )?)
+}
+
+fn breakdown(res: &SimulateJobResponse) -> Vec<(&str, Option<bool>)> {
+    res.condition_breakdown
+        .iter()
+        .map(|node| (node.path.as_str(), node.result))
+        .collect()
+}
+
+#[test]
+fn test_simulate_job() {
+    // the first child decides the or, the second is never evaluated
+    let deps = mock_simulation(
+        Ok(ConditionTrace::Or {
+            conditions: vec![expr_trace(true)],
+            result: true,
+        }),
+        Ok(true),
+    );
+
+    let mut job = mock_create_job_msg("job", 10_000);
+    job.condition = CONDITION.to_string();
+    job.gas_allowance = Some(GasAllowance {
+        budget: Uint128::new(500),
+        gas_price: Decimal::zero(),
+    });
+
+    let res = simulate(&deps, job).unwrap();
+
+    assert_eq!(res.vars, "[]");
+    assert_eq!(res.condition, Some(true));
+    assert_eq!(res.condition_error, None);
+    assert_eq!(breakdown(&res), vec![("", Some(true)), ("0", Some(true))]);
+    assert_eq!(
+        res.condition_breakdown[1].condition,
+        r#"{"expr":{"timestamp":{"comparator":"0","op":"gt"}}}"#
+    );
+    assert_eq!(res.msgs, vec![hydrated_msg()]);
+
+    assert_eq!(
+        res.fees.reward_asset,
+        AssetInfo::Native("uluna".to_string())
+    );
+    assert_eq!(res.fees.creation_fee, Uint128::new(500));
+    assert_eq!(res.fees.gas_budget, Uint128::new(500));
+    assert_eq!(res.fees.total, Uint128::new(11_000));
+}
+
+#[test]
+fn test_simulate_job_condition_error() {
+    let deps = mock_simulation(Err("Invalid block height".to_string()), Ok(true));
+
+    let mut job = mock_create_job_msg("job", 10_000);
+    job.condition = CONDITION.to_string();
+
+    let res = simulate(&deps, job).unwrap();
+
+    assert_eq!(res.condition, None);
+    assert!(res.condition_error.is_some());
+    assert_eq!(breakdown(&res), vec![("", None)]);
+    assert!(res.condition_breakdown[0].error.is_some());
+    assert!(res.msgs.is_empty());
+}
+
+#[test]
+fn test_simulate_job_no_branch_fires() {
+    let deps = mock_simulation(
+        Ok(ConditionTrace::Or {
+            conditions: vec![
+                expr_trace(false),
+                ConditionTrace::Not {
+                    condition: Box::new(expr_trace(true)),
+                    result: false,
+                },
+            ],
+            result: false,
+        }),
+        Ok(false),
+    );
+
+    let mut job = mock_create_job_msg("job", 10_000);
+    job.condition = CONDITION.to_string();
+
+    let res = simulate(&deps, job).unwrap();
+
+    assert_eq!(res.condition, Some(false));
+    assert_eq!(
+        breakdown(&res),
+        vec![
+            ("", Some(false)),
+            ("0", Some(false)),
+            ("1", Some(false)),
+            ("1.0", Some(true))
+        ]
+    );
+    // the job's own msgs would not be sent
+    assert!(res.msgs.is_empty());
+}
+
+#[test]
+fn test_simulate_job_branch_error() {
+    let deps = mock_simulation(
+        Ok(ConditionTrace::Or {
+            conditions: vec![expr_trace(true)],
+            result: true,
+        }),
+        Err("Invalid branch".to_string()),
+    );
+
+    let mut job = mock_create_job_msg("job", 10_000);
+    job.condition = CONDITION.to_string();
+    job.branches = Some(vec![JobBranch {
+        condition: CONDITION.to_string(),
+        msgs: "[]".to_string(),
+    }]);
+
+    assert!(simulate(&deps, job).is_err());
+}
@@ -0,0 +1,72 @@
+use crate::contract::query;
+use crate::state::KEEPERS;
+use controller::keeper::{Keeper, KeeperIndex, KeepersResponse, QueryKeepersMsg};
+use controller::QueryMsg;
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{from_binary, Addr, Uint128, Uint64};
+
+fn keeper(address: &str, executed: u64) -> Keeper {
+    Keeper {
+        address: Addr::unchecked(address),
+        stake: Uint128::new(1_000),
+        unbonding: Uint128::zero(),
+        unbonding_release_at: None,
+        executed: Uint64::new(executed),
+        failed: Uint64::zero(),
+        evicted: Uint64::zero(),
+        slashed: Uint128::zero(),
+        rewards_earned: vec![],
+    }
+}
+
+#[test]
+fn test_query_keepers_ranked_by_executed() {
+    let mut deps = mock_dependencies();
+    for (address, executed) in [("keeper_a", 3), ("keeper_b", 10), ("keeper_c", 7)] {
+        KEEPERS()
+            .save(
+                &mut deps.storage,
+                Addr::unchecked(address),
+                &keeper(address, executed),
+            )
+            .unwrap();
+    }
+
+    let res: KeepersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QueryKeepers(QueryKeepersMsg {
+                start_after: None,
+                limit: Some(2),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.keepers
+            .iter()
+            .map(|k| k.address.as_str())
+            .collect::<Vec<_>>(),
+        vec!["keeper_b", "keeper_c"]
+    );
+
+    let res: KeepersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QueryKeepers(QueryKeepersMsg {
+                start_after: Some(KeeperIndex {
+                    executed: Uint64::new(7),
+                    address: "keeper_c".to_string(),
+                }),
+                limit: None,
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.keepers.len(), 1);
+    assert_eq!(res.keepers[0].address, Addr::unchecked("keeper_a"));
+}
@@ -1,8 +1,17 @@
 mod condition;
 mod filter;
 mod path;
+mod test_asset;
+mod test_branch;
 mod test_condition;
+mod test_dependency;
 mod test_filter;
+mod test_history;
+mod test_limits;
+mod test_output;
 mod test_path;
+mod test_retry;
+mod test_reward;
+mod test_schedule;
 mod test_vars;
 mod variable;
@@ -0,0 +1,80 @@
+use crate::tests::helpers::{mock_config, mock_job};
+use crate::util::asset::{assert_reward_asset_allowed, job_reward_asset, transfer_msg};
+use crate::ContractError;
+use controller::account::AssetInfo;
+use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+#[test]
+fn test_job_reward_asset_defaults_to_fee_denom() {
+    let config = mock_config();
+    let mut job = mock_job(1);
+    assert_eq!(
+        job_reward_asset(&config, &job),
+        AssetInfo::Native("uluna".to_string())
+    );
+
+    job.reward_asset = Some(AssetInfo::Cw20(Addr::unchecked("token")));
+    assert_eq!(
+        job_reward_asset(&config, &job),
+        AssetInfo::Cw20(Addr::unchecked("token"))
+    );
+}
+
+#[test]
+fn test_assert_reward_asset_allowed() {
+    let mut config = mock_config();
+    let token = AssetInfo::Cw20(Addr::unchecked("token"));
+
+    assert!(assert_reward_asset_allowed(&config, &AssetInfo::Native("uluna".to_string())).is_ok());
+    assert_eq!(
+        assert_reward_asset_allowed(&config, &token),
+        Err(ContractError::RewardAssetNotAllowed {})
+    );
+
+    config.reward_assets = vec![token.clone()];
+    assert!(assert_reward_asset_allowed(&config, &token).is_ok());
+    assert_eq!(
+        assert_reward_asset_allowed(
+            &config,
+            &AssetInfo::Cw721(Addr::unchecked("nft"), "1".to_string())
+        ),
+        Err(ContractError::InvalidRewardAsset {})
+    );
+}
+
+#[test]
+fn test_transfer_msg() {
+    let recipient = Addr::unchecked("keeper");
+
+    assert_eq!(
+        transfer_msg(
+            &AssetInfo::Native("uluna".to_string()),
+            Uint128::new(100),
+            &recipient
+        )
+        .unwrap(),
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "keeper".to_string(),
+            amount: vec![Coin::new(100, "uluna")],
+        })
+    );
+
+    assert_eq!(
+        transfer_msg(
+            &AssetInfo::Cw20(Addr::unchecked("token")),
+            Uint128::new(100),
+            &recipient
+        )
+        .unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "keeper".to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
@@ -0,0 +1,79 @@
+use crate::tests::helpers::{mock_config, mock_job};
+use crate::util::branch::{job_branches, select_branch, validate_branches, MAX_BRANCHES};
+use crate::ContractError;
+use controller::job::JobBranch;
+use cosmwasm_std::testing::mock_dependencies;
+use cosmwasm_std::{from_binary, to_binary, ContractResult, SystemResult, WasmQuery};
+
+fn branch(condition: &str, msgs: &str) -> JobBranch {
+    JobBranch {
+        condition: condition.to_string(),
+        msgs: msgs.to_string(),
+    }
+}
+
+#[test]
+fn test_validate_branches() {
+    assert!(validate_branches(&[]).is_ok());
+    assert!(validate_branches(&vec![branch("", "[]"); MAX_BRANCHES]).is_ok());
+    assert!(matches!(
+        validate_branches(&vec![branch("", "[]"); MAX_BRANCHES + 1]),
+        Err(ContractError::InvalidBranches { .. })
+    ));
+}
+
+#[test]
+fn test_job_branches() {
+    let mut job = mock_job(1);
+    job.branches = vec![branch("stop_loss", "stop_loss_msgs")];
+
+    assert_eq!(
+        job_branches(&job),
+        vec![
+            branch(&job.condition, &job.msgs),
+            branch("stop_loss", "stop_loss_msgs"),
+        ]
+    );
+}
+
+#[test]
+fn test_select_branch() {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => match from_binary(msg).unwrap() {
+            resolver::QueryMsg::QueryResolveBranch(data) => {
+                // only the stop loss condition holds
+                let index = data
+                    .conditions
+                    .iter()
+                    .position(|condition| condition == "stop_loss")
+                    .map(|index| index as u32);
+                SystemResult::Ok(ContractResult::Ok(to_binary(&index).unwrap()))
+            }
+            _ => panic!("unexpected query"),
+        },
+        _ => panic!("unexpected query"),
+    });
+
+    let config = mock_config();
+    let branches = vec![
+        branch("take_profit", "take_profit_msgs"),
+        branch("stop_loss", "stop_loss_msgs"),
+    ];
+
+    assert_eq!(
+        select_branch(deps.as_ref(), &config, &branches, &None, "[]").unwrap(),
+        Some("stop_loss_msgs".to_string())
+    );
+    assert_eq!(
+        select_branch(
+            deps.as_ref(),
+            &config,
+            &branches[..1],
+            &Some("else_msgs".to_string()),
+            "[]"
+        )
+        .unwrap(),
+        Some("else_msgs".to_string())
+    );
+}
@@ -0,0 +1,121 @@
+use crate::state::{FINISHED_JOBS, PENDING_JOBS};
+use crate::tests::helpers::mock_job;
+use crate::util::dependency::{dependencies_met, dependency_graph, validate_dependencies};
+use crate::ContractError;
+use controller::job::{DependencyStatus, JobDependency, JobStatus};
+use cosmwasm_std::testing::MockStorage;
+use cosmwasm_std::Uint64;
+
+fn dependency(job_id: u64, required_status: DependencyStatus) -> JobDependency {
+    JobDependency {
+        job_id: Uint64::new(job_id),
+        required_status,
+    }
+}
+
+#[test]
+fn test_validate_dependencies() {
+    let mut storage = MockStorage::new();
+    PENDING_JOBS().save(&mut storage, 1, &mock_job(1)).unwrap();
+
+    assert!(validate_dependencies(
+        &storage,
+        Uint64::new(2),
+        &[dependency(1, DependencyStatus::Executed)]
+    )
+    .is_ok());
+
+    assert!(matches!(
+        validate_dependencies(
+            &storage,
+            Uint64::new(2),
+            &[dependency(3, DependencyStatus::Any)]
+        ),
+        Err(ContractError::InvalidDependencies { .. })
+    ));
+
+    assert!(matches!(
+        validate_dependencies(
+            &storage,
+            Uint64::new(2),
+            &[
+                dependency(1, DependencyStatus::Any),
+                dependency(1, DependencyStatus::Failed)
+            ]
+        ),
+        Err(ContractError::InvalidDependencies { .. })
+    ));
+}
+
+#[test]
+fn test_validate_dependencies_rejects_cycles() {
+    let mut storage = MockStorage::new();
+
+    assert_eq!(
+        validate_dependencies(
+            &storage,
+            Uint64::new(1),
+            &[dependency(1, DependencyStatus::Any)]
+        ),
+        Err(ContractError::DependencyCycle {})
+    );
+
+    // job 1 depending on a job id that is only created later
+    let mut job = mock_job(1);
+    job.dependencies = vec![dependency(2, DependencyStatus::Any)];
+    PENDING_JOBS().save(&mut storage, 1, &job).unwrap();
+
+    assert_eq!(
+        validate_dependencies(
+            &storage,
+            Uint64::new(2),
+            &[dependency(1, DependencyStatus::Any)]
+        ),
+        Err(ContractError::DependencyCycle {})
+    );
+}
+
+#[test]
+fn test_dependencies_met() {
+    let mut storage = MockStorage::new();
+
+    let mut executed = mock_job(1);
+    executed.status = JobStatus::Executed;
+    FINISHED_JOBS().save(&mut storage, 1, &executed).unwrap();
+    PENDING_JOBS().save(&mut storage, 2, &mock_job(2)).unwrap();
+
+    let mut job = mock_job(3);
+    job.dependencies = vec![dependency(1, DependencyStatus::Executed)];
+    assert!(dependencies_met(&storage, &job).unwrap());
+
+    job.dependencies = vec![dependency(1, DependencyStatus::Failed)];
+    assert!(!dependencies_met(&storage, &job).unwrap());
+
+    job.dependencies = vec![
+        dependency(1, DependencyStatus::Any),
+        dependency(2, DependencyStatus::Any),
+    ];
+    assert!(!dependencies_met(&storage, &job).unwrap());
+}
+
+#[test]
+fn test_dependency_graph() {
+    let mut storage = MockStorage::new();
+    PENDING_JOBS().save(&mut storage, 1, &mock_job(1)).unwrap();
+
+    let mut job_2 = mock_job(2);
+    job_2.dependencies = vec![dependency(1, DependencyStatus::Executed)];
+    PENDING_JOBS().save(&mut storage, 2, &job_2).unwrap();
+
+    let mut job_3 = mock_job(3);
+    job_3.dependencies = vec![
+        dependency(1, DependencyStatus::Any),
+        dependency(2, DependencyStatus::Executed),
+    ];
+
+    let nodes = dependency_graph(&storage, job_3).unwrap();
+    assert_eq!(
+        nodes.iter().map(|n| n.id.u64()).collect::<Vec<_>>(),
+        vec![3, 1, 2]
+    );
+}
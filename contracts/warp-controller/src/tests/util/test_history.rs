@@ -0,0 +1,106 @@
+use crate::query::job::query_job_history;
+use crate::tests::helpers::mock_job;
+use crate::util::history::{record_run, series_id};
+use controller::job::{JobStatus, QueryJobHistoryMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{Addr, Uint128, Uint64};
+
+#[test]
+fn test_series_id() {
+    let mut job = mock_job(3);
+    assert_eq!(series_id(&job), Uint64::new(3));
+
+    job.series_id = Uint64::new(1);
+    assert_eq!(series_id(&job), Uint64::new(1));
+
+    // stored before series were tracked
+    job.series_id = Uint64::zero();
+    assert_eq!(series_id(&job), Uint64::new(3));
+}
+
+#[test]
+fn test_record_run() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let keeper = Addr::unchecked("keeper");
+
+    let first = mock_job(1);
+    let mut requeued = mock_job(2);
+    requeued.series_id = Uint64::new(1);
+
+    assert_eq!(
+        record_run(
+            deps.as_mut().storage,
+            &first,
+            JobStatus::Executed,
+            keeper.clone(),
+            &env.block,
+            Uint128::new(100),
+            None,
+        )
+        .unwrap(),
+        Uint64::new(1)
+    );
+    assert_eq!(
+        record_run(
+            deps.as_mut().storage,
+            &requeued,
+            JobStatus::Failed,
+            keeper.clone(),
+            &env.block,
+            Uint128::new(90),
+            Some("Out of gas.".to_string()),
+        )
+        .unwrap(),
+        Uint64::new(2)
+    );
+    // another series numbers its runs on its own
+    assert_eq!(
+        record_run(
+            deps.as_mut().storage,
+            &mock_job(3),
+            JobStatus::Executed,
+            keeper,
+            &env.block,
+            Uint128::new(100),
+            None,
+        )
+        .unwrap(),
+        Uint64::new(1)
+    );
+
+    let runs = query_job_history(
+        deps.as_ref(),
+        env.clone(),
+        QueryJobHistoryMsg {
+            series_id: Uint64::new(1),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap()
+    .runs;
+
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].job_id, Uint64::new(1));
+    assert_eq!(runs[0].status, JobStatus::Executed);
+    assert_eq!(runs[0].height, Uint64::new(env.block.height));
+    assert_eq!(runs[1].job_id, Uint64::new(2));
+    assert_eq!(runs[1].reward_paid, Uint128::new(90));
+    assert_eq!(runs[1].error, Some("Out of gas.".to_string()));
+
+    let runs = query_job_history(
+        deps.as_ref(),
+        env,
+        QueryJobHistoryMsg {
+            series_id: Uint64::new(1),
+            start_after: Some(Uint64::new(1)),
+            limit: Some(1),
+        },
+    )
+    .unwrap()
+    .runs;
+
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].run, Uint64::new(2));
+}
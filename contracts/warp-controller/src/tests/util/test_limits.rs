@@ -0,0 +1,104 @@
+use crate::error::{is_out_of_gas_error, job_failure_reason};
+use crate::tests::helpers::{mock_config, mock_job};
+use crate::util::limits::{is_expired, job_gas_limit, max_executions_reached, validate_gas_limit};
+use crate::ContractError;
+use controller::job::JobFailureReason;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Uint64;
+use cw_utils::Expiration;
+
+#[test]
+fn test_max_executions_reached() {
+    let mut job = mock_job(1);
+    job.executions = Uint64::new(3);
+    assert!(!max_executions_reached(&job));
+
+    job.max_executions = Some(Uint64::new(4));
+    assert!(!max_executions_reached(&job));
+
+    job.executions = Uint64::new(4);
+    assert!(max_executions_reached(&job));
+}
+
+#[test]
+fn test_is_expired() {
+    let env = mock_env();
+    let mut job = mock_job(1);
+    assert!(!is_expired(&job, &env.block));
+
+    job.expires_at = Some(Expiration::AtHeight(env.block.height + 1));
+    assert!(!is_expired(&job, &env.block));
+
+    job.expires_at = Some(Expiration::AtHeight(env.block.height));
+    assert!(is_expired(&job, &env.block));
+}
+
+#[test]
+fn test_validate_gas_limit() {
+    let mut config = mock_config();
+    assert!(validate_gas_limit(&config, None).is_ok());
+    assert!(validate_gas_limit(&config, Some(Uint64::new(5_000_000))).is_ok());
+    assert_eq!(
+        validate_gas_limit(&config, Some(Uint64::zero())),
+        Err(ContractError::InvalidGasLimit {})
+    );
+
+    config.max_job_gas_limit = Some(Uint64::new(1_000_000));
+    assert!(validate_gas_limit(&config, Some(Uint64::new(1_000_000))).is_ok());
+    assert_eq!(
+        validate_gas_limit(&config, Some(Uint64::new(1_000_001))),
+        Err(ContractError::InvalidGasLimit {})
+    );
+}
+
+#[test]
+fn test_job_gas_limit() {
+    let mut config = mock_config();
+    let mut job = mock_job(1);
+    assert_eq!(job_gas_limit(&config, &job), None);
+
+    job.gas_limit = Some(Uint64::new(500_000));
+    assert_eq!(job_gas_limit(&config, &job), Some(500_000));
+
+    config.max_job_gas_limit = Some(Uint64::new(200_000));
+    assert_eq!(job_gas_limit(&config, &job), Some(200_000));
+
+    job.gas_limit = None;
+    assert_eq!(job_gas_limit(&config, &job), Some(200_000));
+}
+
+#[test]
+fn test_is_out_of_gas_error() {
+    assert!(is_out_of_gas_error(
+        "codespace: sdk, code: 11: out of gas in location: wasm contract"
+    ));
+    assert!(!is_out_of_gas_error("codespace: wasm, code: 5"));
+}
+
+#[test]
+fn test_job_failure_reason() {
+    assert_eq!(
+        job_failure_reason("codespace: sdk, code: 11: out of gas in location: wasm contract"),
+        JobFailureReason::OutOfGas
+    );
+    assert!(matches!(
+        job_failure_reason("codespace: sdk, code: 5"),
+        JobFailureReason::InsufficientFunds { .. }
+    ));
+    assert_eq!(
+        job_failure_reason("codespace: wasm, code: 28"),
+        JobFailureReason::SubmsgError {
+            module: Some("wasm".to_string()),
+            code: Some(28),
+            msg: "No such code ID.".to_string(),
+        }
+    );
+    assert_eq!(
+        job_failure_reason("Generic error"),
+        JobFailureReason::SubmsgError {
+            module: None,
+            code: None,
+            msg: "Undefined error.".to_string(),
+        }
+    );
+}
@@ -0,0 +1,30 @@
+use crate::util::output::validate_output_bindings;
+use crate::ContractError;
+use controller::job::{OutputBinding, OutputSource};
+
+const VARS: &str = "[{\"static\":{\"kind\":\"uint\",\"name\":\"received\",\"encode\":false,\"value\":\"0\",\"update_fn\":null}}]";
+
+fn binding(var: &str) -> OutputBinding {
+    OutputBinding {
+        var: var.to_string(),
+        source: OutputSource::EventAttribute {
+            event_type: "wasm".to_string(),
+            key: "return_amount".to_string(),
+        },
+        selector: None,
+    }
+}
+
+#[test]
+fn test_validate_output_bindings() {
+    assert!(validate_output_bindings("[]", &[]).is_ok());
+    assert!(validate_output_bindings(VARS, &[binding("received")]).is_ok());
+    assert!(matches!(
+        validate_output_bindings(VARS, &[binding("missing")]),
+        Err(ContractError::InvalidOutputBindings { .. })
+    ));
+    assert!(matches!(
+        validate_output_bindings(VARS, &[binding("received"), binding("received")]),
+        Err(ContractError::InvalidOutputBindings { .. })
+    ));
+}
@@ -0,0 +1,57 @@
+use crate::tests::helpers::mock_job;
+use crate::util::retry::{retry_eligibility, should_retry, validate_retry_policy, MAX_RETRIES};
+use crate::ContractError;
+use controller::job::RetryPolicy;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Uint64;
+use cw_utils::{Duration, Expiration};
+
+fn retry_policy(max_retries: u64) -> Option<RetryPolicy> {
+    Some(RetryPolicy {
+        max_retries: Uint64::new(max_retries),
+        backoff: Duration::Time(60),
+    })
+}
+
+#[test]
+fn test_validate_retry_policy() {
+    assert!(validate_retry_policy(&None).is_ok());
+    assert!(validate_retry_policy(&retry_policy(3)).is_ok());
+    assert_eq!(
+        validate_retry_policy(&retry_policy(0)),
+        Err(ContractError::InvalidRetryPolicy { max: MAX_RETRIES })
+    );
+    assert_eq!(
+        validate_retry_policy(&retry_policy(MAX_RETRIES + 1)),
+        Err(ContractError::InvalidRetryPolicy { max: MAX_RETRIES })
+    );
+}
+
+#[test]
+fn test_should_retry() {
+    let env = mock_env();
+    let mut job = mock_job(1);
+    assert!(!should_retry(&job, &env.block));
+
+    job.retry_policy = retry_policy(3);
+    job.retries_left = Uint64::new(1);
+    assert!(should_retry(&job, &env.block));
+
+    job.retries_left = Uint64::zero();
+    assert!(!should_retry(&job, &env.block));
+
+    job.retries_left = Uint64::new(1);
+    job.expires_at = Some(Expiration::AtHeight(env.block.height));
+    assert!(!should_retry(&job, &env.block));
+}
+
+#[test]
+fn test_retry_eligibility() {
+    let env = mock_env();
+
+    assert_eq!(retry_eligibility(&None, &env.block), None);
+    assert_eq!(
+        retry_eligibility(&retry_policy(1), &env.block),
+        Some(Expiration::AtTime(env.block.time.plus_seconds(60)))
+    );
+}
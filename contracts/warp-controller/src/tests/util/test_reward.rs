@@ -0,0 +1,159 @@
+use crate::tests::helpers::{mock_config, mock_job};
+use crate::util::reward::{
+    effective_reward, eviction_fee, gas_allowance, gas_budget, validate_gas_allowance,
+    validate_reward_curve,
+};
+use crate::ContractError;
+use controller::account::AssetInfo;
+use controller::job::{GasAllowance, RewardCurve, RewardRamp};
+use cosmwasm_std::{Decimal, Uint128, Uint64};
+
+#[test]
+fn test_effective_reward_without_curve() {
+    let job = mock_job(1);
+    assert_eq!(effective_reward(&job, 1_000), job.reward);
+}
+
+#[test]
+fn test_effective_reward_linear() {
+    let mut job = mock_job(1);
+    job.last_update_time = Uint64::new(1_000);
+    job.reward_curve = Some(RewardCurve {
+        start_reward: Uint128::new(200),
+        ramp: RewardRamp::Linear {
+            duration: Uint64::new(100),
+        },
+    });
+
+    assert_eq!(effective_reward(&job, 1_000), Uint128::new(200));
+    assert_eq!(effective_reward(&job, 1_050), Uint128::new(600));
+    assert_eq!(effective_reward(&job, 1_100), Uint128::new(1_000));
+    assert_eq!(effective_reward(&job, 5_000), Uint128::new(1_000));
+}
+
+#[test]
+fn test_effective_reward_exponential() {
+    let mut job = mock_job(1);
+    job.last_update_time = Uint64::new(1_000);
+    job.reward_curve = Some(RewardCurve {
+        start_reward: Uint128::new(100),
+        ramp: RewardRamp::Exponential {
+            doubling_period: Uint64::new(60),
+        },
+    });
+
+    assert_eq!(effective_reward(&job, 1_000), Uint128::new(100));
+    assert_eq!(effective_reward(&job, 1_030), Uint128::new(150));
+    assert_eq!(effective_reward(&job, 1_120), Uint128::new(400));
+    assert_eq!(effective_reward(&job, 1_240), Uint128::new(1_000));
+    assert_eq!(effective_reward(&job, u64::MAX), Uint128::new(1_000));
+}
+
+#[test]
+fn test_validate_reward_curve() {
+    let reward = Uint128::new(1_000);
+    let curve = |start_reward: u128, duration: u64| {
+        Some(RewardCurve {
+            start_reward: Uint128::new(start_reward),
+            ramp: RewardRamp::Linear {
+                duration: Uint64::new(duration),
+            },
+        })
+    };
+
+    assert!(validate_reward_curve(&None, reward).is_ok());
+    assert!(validate_reward_curve(&curve(100, 60), reward).is_ok());
+    assert_eq!(
+        validate_reward_curve(&curve(0, 60), reward),
+        Err(ContractError::InvalidRewardCurve {})
+    );
+    assert_eq!(
+        validate_reward_curve(&curve(1_001, 60), reward),
+        Err(ContractError::InvalidRewardCurve {})
+    );
+    assert_eq!(
+        validate_reward_curve(&curve(100, 0), reward),
+        Err(ContractError::InvalidRewardCurve {})
+    );
+}
+
+#[test]
+fn test_gas_allowance() {
+    let mut config = mock_config();
+    let mut job = mock_job(1);
+
+    assert_eq!(gas_budget(&job), Uint128::zero());
+    assert_eq!(gas_allowance(&config, &job), Uint128::zero());
+
+    job.gas_allowance = Some(GasAllowance {
+        budget: Uint128::new(5_000),
+        gas_price: Decimal::from_ratio(15u128, 1_000u128),
+    });
+    job.gas_limit = Some(Uint64::new(200_000));
+
+    assert_eq!(gas_budget(&job), Uint128::new(5_000));
+    assert_eq!(gas_allowance(&config, &job), Uint128::new(3_000));
+
+    // capped by the budget
+    job.gas_limit = Some(Uint64::new(1_000_000));
+    assert_eq!(gas_allowance(&config, &job), Uint128::new(5_000));
+
+    // falls back to the config max
+    job.gas_limit = None;
+    config.max_job_gas_limit = Some(Uint64::new(100_000));
+    assert_eq!(gas_allowance(&config, &job), Uint128::new(1_500));
+}
+
+#[test]
+fn test_validate_gas_allowance() {
+    let mut config = mock_config();
+    let allowance = |budget: u128, gas_price: Decimal| {
+        Some(GasAllowance {
+            budget: Uint128::new(budget),
+            gas_price,
+        })
+    };
+    let price = Decimal::from_ratio(15u128, 1_000u128);
+
+    assert!(validate_gas_allowance(&config, &None, None).is_ok());
+    assert!(validate_gas_allowance(&config, &allowance(100, price), Some(Uint64::new(1))).is_ok());
+    assert_eq!(
+        validate_gas_allowance(&config, &allowance(100, price), None).unwrap_err(),
+        ContractError::InvalidGasAllowance {}
+    );
+    assert_eq!(
+        validate_gas_allowance(&config, &allowance(0, price), Some(Uint64::new(1))).unwrap_err(),
+        ContractError::InvalidGasAllowance {}
+    );
+    assert_eq!(
+        validate_gas_allowance(
+            &config,
+            &allowance(100, Decimal::zero()),
+            Some(Uint64::new(1))
+        )
+        .unwrap_err(),
+        ContractError::InvalidGasAllowance {}
+    );
+
+    config.max_job_gas_limit = Some(Uint64::new(100_000));
+    assert!(validate_gas_allowance(&config, &allowance(100, price), None).is_ok());
+}
+
+#[test]
+fn test_eviction_fee() {
+    let config = mock_config();
+    let mut job = mock_job(1);
+    job.reward = Uint128::new(50_000);
+
+    // fee_denom jobs pay the configured amount as is
+    assert_eq!(
+        eviction_fee(&config, &job, Uint128::new(10_000)),
+        Uint128::new(10_000)
+    );
+
+    job.reward_asset = Some(AssetInfo::Native("uusd".to_string()));
+    assert_eq!(
+        eviction_fee(&config, &job, Uint128::new(2_000)),
+        Uint128::new(10_000)
+    );
+}
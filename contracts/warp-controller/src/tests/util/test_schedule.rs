@@ -0,0 +1,171 @@
+use crate::util::schedule::{
+    exclusivity_end, initial_eligibility, next_cron_time, next_eligibility, requeued_eligibility,
+};
+use crate::ContractError;
+use controller::job::{JobExclusivity, JobSchedule};
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Addr, Timestamp};
+use cw_utils::{Duration, Expiration};
+
+// 2023-11-01T00:00:00Z, a wednesday
+const NOV_1_2023: u64 = 1_698_796_800;
+
+#[test]
+fn test_next_cron_time_every_minute() {
+    assert_eq!(
+        next_cron_time("* * * * *", NOV_1_2023 + 30).unwrap(),
+        NOV_1_2023 + 60
+    );
+}
+
+#[test]
+fn test_next_cron_time_is_strictly_after() {
+    assert_eq!(
+        next_cron_time("0 0 * * *", NOV_1_2023).unwrap(),
+        NOV_1_2023 + 86_400
+    );
+}
+
+#[test]
+fn test_next_cron_time_steps_and_ranges() {
+    // every 15 minutes between 09:00 and 10:59
+    assert_eq!(
+        next_cron_time("*/15 9-10 * * *", NOV_1_2023 + 10 * 3600 + 50 * 60).unwrap(),
+        NOV_1_2023 + 86_400 + 9 * 3600
+    );
+}
+
+#[test]
+fn test_next_cron_time_day_of_week() {
+    // next monday is 2023-11-06
+    assert_eq!(
+        next_cron_time("30 12 * * 1", NOV_1_2023).unwrap(),
+        NOV_1_2023 + 5 * 86_400 + 12 * 3600 + 30 * 60
+    );
+}
+
+#[test]
+fn test_next_cron_time_first_of_month() {
+    // 2023-12-01T00:00:00Z
+    assert_eq!(
+        next_cron_time("0 0 1 * *", NOV_1_2023).unwrap(),
+        1_701_388_800
+    );
+}
+
+#[test]
+fn test_next_cron_time_leap_day() {
+    // 2024-02-29T00:00:00Z
+    assert_eq!(
+        next_cron_time("0 0 29 2 *", NOV_1_2023).unwrap(),
+        1_709_164_800
+    );
+}
+
+#[test]
+fn test_next_cron_time_invalid() {
+    assert!(matches!(
+        next_cron_time("* * *", NOV_1_2023),
+        Err(ContractError::InvalidSchedule { .. })
+    ));
+    assert!(matches!(
+        next_cron_time("60 * * * *", NOV_1_2023),
+        Err(ContractError::InvalidSchedule { .. })
+    ));
+    assert!(matches!(
+        next_cron_time("*/0 * * * *", NOV_1_2023),
+        Err(ContractError::InvalidSchedule { .. })
+    ));
+    assert!(matches!(
+        next_cron_time("0 0 30 2 *", NOV_1_2023),
+        Err(ContractError::InvalidSchedule { .. })
+    ));
+}
+
+#[test]
+fn test_interval_eligibility() {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(NOV_1_2023);
+
+    let schedule = Some(JobSchedule::Interval(Duration::Height(10)));
+
+    assert_eq!(initial_eligibility(&schedule, &env.block).unwrap(), None);
+    assert_eq!(
+        next_eligibility(&schedule, &env.block).unwrap(),
+        Some(Expiration::AtHeight(env.block.height + 10))
+    );
+
+    assert!(
+        initial_eligibility(&Some(JobSchedule::Interval(Duration::Time(0))), &env.block).is_err()
+    );
+}
+
+#[test]
+fn test_cron_eligibility() {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(NOV_1_2023);
+
+    let schedule = Some(JobSchedule::Cron("0 8 * * *".to_string()));
+
+    assert_eq!(
+        initial_eligibility(&schedule, &env.block).unwrap(),
+        Some(Expiration::AtTime(Timestamp::from_seconds(
+            NOV_1_2023 + 8 * 3600
+        )))
+    );
+}
+
+#[test]
+fn test_requeued_eligibility() {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(NOV_1_2023);
+
+    let schedule = Some(JobSchedule::Cron("0 8 * * *".to_string()));
+
+    // an eligibility still ahead is kept
+    let pending = Some(Expiration::AtTime(Timestamp::from_seconds(NOV_1_2023 + 60)));
+    assert_eq!(
+        requeued_eligibility(&schedule, &pending, &env.block).unwrap(),
+        pending
+    );
+
+    // a passed one moves to the next match
+    let passed = Some(Expiration::AtTime(Timestamp::from_seconds(NOV_1_2023 - 60)));
+    assert_eq!(
+        requeued_eligibility(&schedule, &passed, &env.block).unwrap(),
+        Some(Expiration::AtTime(Timestamp::from_seconds(
+            NOV_1_2023 + 8 * 3600
+        )))
+    );
+    assert_eq!(
+        requeued_eligibility(
+            &Some(JobSchedule::Interval(Duration::Height(10))),
+            &Some(Expiration::AtHeight(env.block.height)),
+            &env.block
+        )
+        .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_exclusivity_end() {
+    let env = mock_env();
+    let exclusivity = Some(JobExclusivity {
+        keepers: vec![Addr::unchecked("keeper")],
+        window: Duration::Time(60),
+    });
+
+    assert_eq!(exclusivity_end(&None, &None, &env.block), None);
+    assert_eq!(
+        exclusivity_end(&exclusivity, &None, &env.block),
+        Some(Expiration::AtTime(env.block.time.plus_seconds(60)))
+    );
+
+    // the window opens when a scheduled job becomes eligible
+    let eligible_at = Some(Expiration::AtTime(env.block.time.plus_seconds(600)));
+    assert_eq!(
+        exclusivity_end(&exclusivity, &eligible_at, &env.block),
+        Some(Expiration::AtTime(env.block.time.plus_seconds(660)))
+    );
+}
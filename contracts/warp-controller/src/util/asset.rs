@@ -0,0 +1,104 @@
+use crate::ContractError;
+use account::GenericMsg;
+use controller::account::AssetInfo;
+use controller::job::Job;
+use controller::Config;
+use cosmwasm_std::{
+    to_binary, Addr, Api, BankMsg, Coin, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+// jobs created before reward assets were introduced pay out in fee_denom
+pub fn job_reward_asset(config: &Config, job: &Job) -> AssetInfo {
+    job.reward_asset
+        .clone()
+        .unwrap_or_else(|| AssetInfo::Native(config.fee_denom.clone()))
+}
+
+pub fn validate_reward_assets(
+    api: &dyn Api,
+    reward_assets: Vec<AssetInfo>,
+) -> Result<Vec<AssetInfo>, ContractError> {
+    let mut validated = vec![];
+
+    for asset in reward_assets {
+        validated.push(match asset {
+            AssetInfo::Native(denom) => AssetInfo::Native(denom),
+            AssetInfo::Cw20(addr) => AssetInfo::Cw20(api.addr_validate(addr.as_str())?),
+            AssetInfo::Cw721(_, _) => return Err(ContractError::InvalidRewardAsset {}),
+        })
+    }
+
+    Ok(validated)
+}
+
+pub fn assert_reward_asset_allowed(
+    config: &Config,
+    asset: &AssetInfo,
+) -> Result<(), ContractError> {
+    match asset {
+        AssetInfo::Native(denom) if *denom == config.fee_denom => Ok(()),
+        AssetInfo::Cw721(_, _) => Err(ContractError::InvalidRewardAsset {}),
+        _ if config.reward_assets.contains(asset) => Ok(()),
+        _ => Err(ContractError::RewardAssetNotAllowed {}),
+    }
+}
+
+// transfer held by the controller
+pub fn transfer_msg(
+    asset: &AssetInfo,
+    amount: Uint128,
+    recipient: &Addr,
+) -> Result<CosmosMsg, ContractError> {
+    Ok(match asset {
+        AssetInfo::Native(denom) => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin::new(amount.u128(), denom.clone())],
+        }),
+        AssetInfo::Cw20(token) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+        AssetInfo::Cw721(_, _) => return Err(ContractError::InvalidRewardAsset {}),
+    })
+}
+
+// transfer out of a warp account, executed by the account on behalf of the controller
+pub fn account_transfer_msg(
+    account: &Addr,
+    asset: &AssetInfo,
+    amount: Uint128,
+    recipient: &Addr,
+) -> Result<CosmosMsg, ContractError> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: account.to_string(),
+        msg: to_binary(&account::ExecuteMsg::Generic(GenericMsg {
+            msgs: vec![transfer_msg(asset, amount, recipient)?],
+        }))?,
+        funds: vec![],
+    }))
+}
+
+pub fn query_asset_balance(
+    querier: &QuerierWrapper,
+    asset: &AssetInfo,
+    address: &Addr,
+) -> StdResult<Uint128> {
+    match asset {
+        AssetInfo::Native(denom) => Ok(querier.query_balance(address, denom)?.amount),
+        AssetInfo::Cw20(token) => {
+            let balance: cw20::BalanceResponse = querier.query_wasm_smart(
+                token,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )?;
+            Ok(balance.balance)
+        }
+        AssetInfo::Cw721(_, _) => Ok(Uint128::zero()),
+    }
+}
@@ -0,0 +1,60 @@
+use crate::ContractError;
+use controller::job::{Job, JobBranch};
+use controller::Config;
+use cosmwasm_std::{Deps, StdResult};
+
+pub const MAX_BRANCHES: usize = 10;
+
+pub fn validate_branches(branches: &[JobBranch]) -> Result<(), ContractError> {
+    if branches.len() > MAX_BRANCHES {
+        return Err(ContractError::InvalidBranches {
+            msg: format!("A job can have at most {} branches.", MAX_BRANCHES),
+        });
+    }
+
+    Ok(())
+}
+
+// every branch of the job in resolution order, its own condition and msgs first
+pub fn job_branches(job: &Job) -> Vec<JobBranch> {
+    let mut branches = vec![JobBranch {
+        condition: job.condition.clone(),
+        msgs: job.msgs.clone(),
+    }];
+    branches.extend(job.branches.iter().cloned());
+    branches
+}
+
+// msgs of the first branch whose condition holds, falling back to the else msgs
+pub fn select_branch(
+    deps: Deps,
+    config: &Config,
+    branches: &[JobBranch],
+    else_msgs: &Option<String>,
+    vars: &str,
+) -> StdResult<Option<String>> {
+    // plain jobs keep resolving their single condition
+    if let ([branch], None) = (branches, else_msgs) {
+        let resolution: bool = deps.querier.query_wasm_smart(
+            config.resolver_address.clone(),
+            &resolver::QueryMsg::QueryResolveCondition(resolver::QueryResolveConditionMsg {
+                condition: branch.condition.clone(),
+                vars: vars.to_string(),
+            }),
+        )?;
+        return Ok(resolution.then(|| branch.msgs.clone()));
+    }
+
+    let index: Option<u32> = deps.querier.query_wasm_smart(
+        config.resolver_address.clone(),
+        &resolver::QueryMsg::QueryResolveBranch(resolver::QueryResolveBranchMsg {
+            conditions: branches.iter().map(|b| b.condition.clone()).collect(),
+            vars: vars.to_string(),
+        }),
+    )?;
+
+    Ok(match index {
+        Some(index) => branches.get(index as usize).map(|b| b.msgs.clone()),
+        None => else_msgs.clone(),
+    })
+}
@@ -0,0 +1,153 @@
+use crate::state::{FINISHED_JOBS, JOB_SUMMARIES, PAUSED_JOBS, PENDING_JOBS};
+use crate::ContractError;
+use controller::job::{DependencyStatus, Job, JobDependency, JobDependencyNode, JobStatus};
+use cosmwasm_std::{StdResult, Storage, Uint64};
+use std::collections::{HashSet, VecDeque};
+
+pub const MAX_DEPENDENCIES: usize = 10;
+
+pub fn load_job(storage: &dyn Storage, id: u64) -> StdResult<Option<Job>> {
+    if let Some(job) = FINISHED_JOBS().may_load(storage, id)? {
+        return Ok(Some(job));
+    }
+    if let Some(job) = PENDING_JOBS().may_load(storage, id)? {
+        return Ok(Some(job));
+    }
+    PAUSED_JOBS().may_load(storage, id)
+}
+
+// dependencies of a job about to be created under job_id
+pub fn validate_dependencies(
+    storage: &dyn Storage,
+    job_id: Uint64,
+    dependencies: &[JobDependency],
+) -> Result<(), ContractError> {
+    if dependencies.len() > MAX_DEPENDENCIES {
+        return Err(ContractError::InvalidDependencies {
+            msg: format!("A job can depend on at most {} jobs.", MAX_DEPENDENCIES),
+        });
+    }
+
+    let mut seen = HashSet::new();
+    for dependency in dependencies {
+        if !seen.insert(dependency.job_id.u64()) {
+            return Err(ContractError::InvalidDependencies {
+                msg: format!("Duplicate dependency on job {}.", dependency.job_id),
+            });
+        }
+
+        if dependency.job_id != job_id
+            && load_job(storage, dependency.job_id.u64())?.is_none()
+            && !JOB_SUMMARIES.has(storage, dependency.job_id.u64())
+        {
+            return Err(ContractError::InvalidDependencies {
+                msg: format!("Job {} does not exist.", dependency.job_id),
+            });
+        }
+    }
+
+    // walk upstream from the new job, any path leading back to a job on the current path is a cycle
+    let mut finished = HashSet::new();
+    let mut path = vec![job_id.u64()];
+    let mut stack = vec![dependencies
+        .iter()
+        .map(|d| d.job_id.u64())
+        .collect::<Vec<_>>()];
+
+    while let Some(pending) = stack.last_mut() {
+        match pending.pop() {
+            None => {
+                stack.pop();
+                if let Some(id) = path.pop() {
+                    finished.insert(id);
+                }
+            }
+            Some(id) => {
+                if path.contains(&id) {
+                    return Err(ContractError::DependencyCycle {});
+                }
+                if finished.contains(&id) {
+                    continue;
+                }
+
+                let upstream = match load_job(storage, id)? {
+                    None => vec![],
+                    Some(job) => job.dependencies.iter().map(|d| d.job_id.u64()).collect(),
+                };
+                path.push(id);
+                stack.push(upstream);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn dependency_met(dependency: &JobDependency, status: &JobStatus) -> bool {
+    match (&dependency.required_status, status) {
+        (_, JobStatus::Pending | JobStatus::Paused) => false,
+        (DependencyStatus::Executed, JobStatus::Executed | JobStatus::Completed) => true,
+        (DependencyStatus::Failed, JobStatus::Failed) => true,
+        (DependencyStatus::Any, _) => true,
+        _ => false,
+    }
+}
+
+// status of a job that left the pending queue for good, pruned jobs included
+fn finished_status(storage: &dyn Storage, id: u64) -> StdResult<Option<JobStatus>> {
+    if let Some(job) = FINISHED_JOBS().may_load(storage, id)? {
+        return Ok(Some(job.status));
+    }
+    Ok(JOB_SUMMARIES
+        .may_load(storage, id)?
+        .map(|summary| summary.status))
+}
+
+pub fn dependencies_met(storage: &dyn Storage, job: &Job) -> StdResult<bool> {
+    for dependency in &job.dependencies {
+        match finished_status(storage, dependency.job_id.u64())? {
+            Some(status) if dependency_met(dependency, &status) => {}
+            _ => return Ok(false),
+        }
+    }
+
+    Ok(true)
+}
+
+// the job and all of its transitive upstream jobs, in breadth first order
+pub fn dependency_graph(storage: &dyn Storage, job: Job) -> StdResult<Vec<JobDependencyNode>> {
+    let mut visited = HashSet::from([job.id.u64()]);
+    let mut queue = VecDeque::from([JobDependencyNode {
+        id: job.id,
+        status: job.status,
+        dependencies: job.dependencies,
+    }]);
+    let mut nodes = vec![];
+
+    while let Some(node) = queue.pop_front() {
+        for dependency in &node.dependencies {
+            if visited.insert(dependency.job_id.u64()) {
+                if let Some(upstream) = load_job(storage, dependency.job_id.u64())? {
+                    queue.push_back(JobDependencyNode {
+                        id: upstream.id,
+                        status: upstream.status,
+                        dependencies: upstream.dependencies,
+                    });
+                } else if let Some(summary) =
+                    JOB_SUMMARIES.may_load(storage, dependency.job_id.u64())?
+                {
+                    // a pruned job no longer knows its own dependencies
+                    queue.push_back(JobDependencyNode {
+                        id: summary.id,
+                        status: summary.status,
+                        dependencies: vec![],
+                    });
+                }
+            }
+        }
+
+        nodes.push(node);
+    }
+
+    Ok(nodes)
+}
@@ -1,26 +1,118 @@
-use controller::job::{Job, JobStatus};
-use cosmwasm_std::{Addr, Deps, Env};
+use crate::util::branch::{job_branches, select_branch};
+use crate::util::limits::is_expired;
+use controller::job::{Job, JobStatus, LabelFilter, QueryJobsMsg};
+use controller::Config;
+use cosmwasm_std::{Deps, Env, StdResult};
 
 pub fn resolve_filters(
-    _deps: Deps,
-    _env: Env,
-    job: Job,
-    name: Option<String>,
-    owner: Option<Addr>,
-    job_status: Option<JobStatus>,
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    job: &Job,
+    filters: &QueryJobsMsg,
 ) -> bool {
     //readability-optimized
-    if job_status.is_some() && job_status.unwrap() != job.status {
-        return false;
+    if let Some(job_status) = &filters.job_status {
+        if *job_status != job.status {
+            return false;
+        }
     }
 
-    if name.is_some() && name.unwrap() != job.name {
-        return false;
+    if let Some(name) = &filters.name {
+        if *name != job.name {
+            return false;
+        }
     }
 
-    if owner.is_some() && owner.unwrap() != job.owner {
-        return false;
+    if let Some(name_prefix) = &filters.name_prefix {
+        if !job.name.starts_with(name_prefix.as_str()) {
+            return false;
+        }
+    }
+
+    if let Some(owner) = &filters.owner {
+        if *owner != job.owner {
+            return false;
+        }
+    }
+
+    if let Some(labels) = &filters.labels {
+        let matches = match labels {
+            LabelFilter::Any(labels) => labels.iter().any(|l| job.labels.contains(l)),
+            LabelFilter::All(labels) => labels.iter().all(|l| job.labels.contains(l)),
+        };
+        if !matches {
+            return false;
+        }
+    }
+
+    if let Some(recurring) = filters.recurring {
+        if recurring != job.recurring {
+            return false;
+        }
+    }
+
+    if let Some(min_reward) = filters.min_reward {
+        if job.reward < min_reward {
+            return false;
+        }
+    }
+
+    if let Some(max_reward) = filters.max_reward {
+        if job.reward > max_reward {
+            return false;
+        }
+    }
+
+    if let Some(updated_after) = filters.updated_after {
+        if job.last_update_time < updated_after {
+            return false;
+        }
+    }
+
+    if let Some(updated_before) = filters.updated_before {
+        if job.last_update_time > updated_before {
+            return false;
+        }
+    }
+
+    if let Some(active) = filters.active {
+        if active != is_active(env, job) {
+            return false;
+        }
+    }
+
+    // most expensive, resolved last
+    if let Some(condition_status) = filters.condition_status {
+        match resolve_condition(deps, config, job) {
+            Ok(resolution) if resolution == condition_status => {}
+            _ => return false,
+        }
     }
 
     true
 }
+
+// a pending job a keeper could pick up at this block
+fn is_active(env: &Env, job: &Job) -> bool {
+    job.status == JobStatus::Pending
+        && !is_expired(job, &env.block)
+        && match &job.eligible_at {
+            None => true,
+            Some(eligible_at) => eligible_at.is_expired(&env.block),
+        }
+}
+
+fn resolve_condition(deps: Deps, config: &Config, job: &Job) -> StdResult<bool> {
+    let vars: String = deps.querier.query_wasm_smart(
+        config.resolver_address.clone(),
+        &resolver::QueryMsg::QueryHydrateVars(resolver::QueryHydrateVarsMsg {
+            vars: job.vars.clone(),
+            external_inputs: None,
+        }),
+    )?;
+
+    // a job is ready once any of its branches would be sent
+    select_branch(deps, config, &job_branches(job), &job.else_msgs, &vars)
+        .map(|msgs| msgs.is_some())
+}
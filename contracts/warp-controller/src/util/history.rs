@@ -0,0 +1,48 @@
+use crate::state::JOB_RUNS;
+use controller::job::{Job, JobRun, JobStatus};
+use cosmwasm_std::{Addr, BlockInfo, Order, StdResult, Storage, Uint128, Uint64};
+
+// jobs stored before series were tracked start a series of their own
+pub fn series_id(job: &Job) -> Uint64 {
+    if job.series_id.is_zero() {
+        job.id
+    } else {
+        job.series_id
+    }
+}
+
+pub fn record_run(
+    storage: &mut dyn Storage,
+    job: &Job,
+    status: JobStatus,
+    keeper: Addr,
+    block: &BlockInfo,
+    reward_paid: Uint128,
+    error: Option<String>,
+) -> StdResult<Uint64> {
+    let series_id = series_id(job).u64();
+    let last = JOB_RUNS
+        .prefix(series_id)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .unwrap_or_default();
+    let run = Uint64::new(last + 1);
+
+    JOB_RUNS.save(
+        storage,
+        (series_id, run.u64()),
+        &JobRun {
+            run,
+            job_id: job.id,
+            status,
+            keeper,
+            height: Uint64::new(block.height),
+            time: Uint64::new(block.time.seconds()),
+            reward_paid,
+            error,
+        },
+    )?;
+
+    Ok(run)
+}
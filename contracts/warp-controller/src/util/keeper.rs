@@ -0,0 +1,59 @@
+use crate::state::KEEPERS;
+use crate::ContractError;
+use controller::account::AssetInfo;
+use controller::keeper::{Keeper, KeeperReward};
+use controller::Config;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128, Uint64};
+
+// registered keepers need the minimum stake, unregistered callers are only allowed when there is
+// neither a minimum stake nor a slash they would escape
+pub fn load_executing_keeper(
+    storage: &dyn Storage,
+    config: &Config,
+    sender: &Addr,
+) -> Result<Option<Keeper>, ContractError> {
+    match KEEPERS().may_load(storage, sender.clone())? {
+        None if config.keeper_min_stake.is_zero() && config.keeper_slash_percentage.is_zero() => {
+            Ok(None)
+        }
+        None => Err(ContractError::KeeperNotRegistered {}),
+        Some(keeper) if keeper.stake < config.keeper_min_stake => {
+            Err(ContractError::InsufficientKeeperStake {})
+        }
+        Some(keeper) => Ok(Some(keeper)),
+    }
+}
+
+// takes the configured share of the bonded and unbonding stake, the caller sends it to the fee collector
+pub fn slash_keeper(config: &Config, keeper: &mut Keeper) -> StdResult<Uint128> {
+    let from_stake = keeper
+        .stake
+        .multiply_ratio(config.keeper_slash_percentage.u64(), 100u64);
+    let from_unbonding = keeper
+        .unbonding
+        .multiply_ratio(config.keeper_slash_percentage.u64(), 100u64);
+
+    keeper.stake = keeper.stake.checked_sub(from_stake)?;
+    keeper.unbonding = keeper.unbonding.checked_sub(from_unbonding)?;
+
+    let amount = from_stake.checked_add(from_unbonding)?;
+    keeper.slashed = keeper.slashed.checked_add(amount)?;
+
+    Ok(amount)
+}
+
+pub fn add_keeper_reward(keeper: &mut Keeper, asset: &AssetInfo, amount: Uint128) -> StdResult<()> {
+    match keeper.rewards_earned.iter_mut().find(|r| r.asset == *asset) {
+        Some(reward) => reward.amount = reward.amount.checked_add(amount)?,
+        None => keeper.rewards_earned.push(KeeperReward {
+            asset: asset.clone(),
+            amount,
+        }),
+    }
+    Ok(())
+}
+
+pub fn increment(counter: &mut Uint64) -> StdResult<()> {
+    *counter = counter.checked_add(Uint64::one())?;
+    Ok(())
+}
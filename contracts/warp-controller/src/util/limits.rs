@@ -0,0 +1,47 @@
+use crate::state::FINISHED_JOBS;
+use crate::ContractError;
+use controller::job::{Job, JobStatus};
+use controller::Config;
+use cosmwasm_std::{BlockInfo, StdResult, Storage, Uint64};
+
+pub fn max_executions_reached(job: &Job) -> bool {
+    match job.max_executions {
+        None => false,
+        Some(max_executions) => job.executions >= max_executions,
+    }
+}
+
+pub fn is_expired(job: &Job, block: &BlockInfo) -> bool {
+    match &job.expires_at {
+        None => false,
+        Some(expires_at) => expires_at.is_expired(block),
+    }
+}
+
+// a recurring job stopped by its limits ends with a terminal status instead of its last run's
+pub fn finish_with_status(
+    storage: &mut dyn Storage,
+    job: &Job,
+    status: JobStatus,
+) -> StdResult<()> {
+    let mut finished_job = job.clone();
+    finished_job.status = status;
+    FINISHED_JOBS().save(storage, job.id.u64(), &finished_job)
+}
+
+pub fn validate_gas_limit(config: &Config, gas_limit: Option<Uint64>) -> Result<(), ContractError> {
+    match (gas_limit, config.max_job_gas_limit) {
+        (Some(gas_limit), _) if gas_limit.is_zero() => Err(ContractError::InvalidGasLimit {}),
+        (Some(gas_limit), Some(max)) if gas_limit > max => Err(ContractError::InvalidGasLimit {}),
+        _ => Ok(()),
+    }
+}
+
+// the config maximum may have been lowered since the job was created
+pub fn job_gas_limit(config: &Config, job: &Job) -> Option<u64> {
+    match (job.gas_limit, config.max_job_gas_limit) {
+        (Some(gas_limit), Some(max)) => Some(gas_limit.min(max).u64()),
+        (Some(gas_limit), None) => Some(gas_limit.u64()),
+        (None, max) => max.map(|max| max.u64()),
+    }
+}
@@ -1 +1,13 @@
+pub(crate) mod asset;
+pub(crate) mod branch;
+pub(crate) mod dependency;
 pub(crate) mod filter;
+pub(crate) mod history;
+pub(crate) mod keeper;
+pub(crate) mod limits;
+pub(crate) mod output;
+pub(crate) mod policy;
+pub(crate) mod retry;
+pub(crate) mod reward;
+pub(crate) mod schedule;
+pub(crate) mod summary;
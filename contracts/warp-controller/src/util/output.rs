@@ -0,0 +1,66 @@
+use crate::ContractError;
+use controller::job::{Job, OutputBinding};
+use controller::Config;
+use cosmwasm_std::{Deps, Reply, SubMsgResult};
+use resolver::variable::Variable;
+use std::collections::HashSet;
+
+// every binding writes to its own static variable
+pub fn validate_output_bindings(
+    vars: &str,
+    bindings: &[OutputBinding],
+) -> Result<(), ContractError> {
+    if bindings.is_empty() {
+        return Ok(());
+    }
+
+    let vars: Vec<Variable> = serde_json_wasm::from_str(vars)
+        .map_err(|e| ContractError::InvalidOutputBindings { msg: e.to_string() })?;
+
+    let mut seen = HashSet::new();
+    for binding in bindings {
+        if !seen.insert(binding.var.as_str()) {
+            return Err(ContractError::InvalidOutputBindings {
+                msg: format!("Variable {} is bound more than once.", binding.var),
+            });
+        }
+
+        let is_static = vars
+            .iter()
+            .any(|var| matches!(var, Variable::Static(v) if v.name == binding.var));
+        if !is_static {
+            return Err(ContractError::InvalidOutputBindings {
+                msg: format!("Variable {} is not a static variable.", binding.var),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// vars with the outputs of a successful run captured, a capture error leaves them unchanged
+pub fn capture_outputs(
+    deps: Deps,
+    config: &Config,
+    job: &Job,
+    msg: &Reply,
+) -> (String, Option<String>) {
+    let events = match &msg.result {
+        SubMsgResult::Ok(response) if !job.output_bindings.is_empty() => response.events.clone(),
+        _ => return (job.vars.clone(), None),
+    };
+
+    let vars: Result<String, _> = deps.querier.query_wasm_smart(
+        config.resolver_address.clone(),
+        &resolver::QueryMsg::QueryCaptureOutputs(resolver::QueryCaptureOutputsMsg {
+            vars: job.vars.clone(),
+            bindings: job.output_bindings.clone(),
+            events,
+        }),
+    );
+
+    match vars {
+        Ok(vars) => (vars, None),
+        Err(e) => (job.vars.clone(), Some(e.to_string())),
+    }
+}
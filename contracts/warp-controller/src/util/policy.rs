@@ -0,0 +1,48 @@
+use crate::ContractError;
+use account::{GenericMsg, GenericWithPolicyMsg, PolicyMsg};
+use controller::account::{MsgExecutionSummary, MsgPolicy, MsgResult};
+use controller::job::Job;
+use controller::Config;
+use cosmwasm_std::{from_binary, Addr, CosmosMsg, QuerierWrapper, Reply};
+use cw_utils::parse_reply_execute_data;
+
+// accounts instantiated from older code don't know GenericWithPolicy, MigrateAccounts moves them
+// to the current code
+pub fn assert_account_supports_policies(
+    querier: &QuerierWrapper,
+    config: &Config,
+    account: &Addr,
+) -> Result<(), ContractError> {
+    let info = querier.query_wasm_contract_info(account)?;
+    if info.code_id != config.warp_account_code_id.u64() {
+        return Err(ContractError::AccountOutdated {});
+    }
+    Ok(())
+}
+
+// msgs without a policy of their own are required
+pub fn account_execute_msg(job: &Job, msgs: Vec<CosmosMsg>) -> account::ExecuteMsg {
+    match &job.msg_policies {
+        None => account::ExecuteMsg::Generic(GenericMsg { msgs }),
+        Some(policies) => account::ExecuteMsg::GenericWithPolicy(GenericWithPolicyMsg {
+            msgs: msgs
+                .into_iter()
+                .enumerate()
+                .map(|(index, msg)| PolicyMsg {
+                    msg,
+                    policy: policies.get(index).cloned().unwrap_or(MsgPolicy::Required),
+                })
+                .collect(),
+        }),
+    }
+}
+
+// per msg results reported by the account, none for a failed run or a job without msg policies
+pub fn msg_results(job: &Job, msg: &Reply) -> Option<Vec<MsgResult>> {
+    job.msg_policies.as_ref()?;
+
+    let data = parse_reply_execute_data(msg.clone()).ok()?.data?;
+    from_binary::<MsgExecutionSummary>(&data)
+        .ok()
+        .map(|summary| summary.results)
+}
@@ -0,0 +1,39 @@
+use crate::util::limits::is_expired;
+use crate::ContractError;
+use controller::job::{Job, RetryPolicy};
+use cosmwasm_std::{BlockInfo, Uint64};
+use cw_utils::Expiration;
+
+pub const MAX_RETRIES: u64 = 10;
+
+pub fn validate_retry_policy(retry_policy: &Option<RetryPolicy>) -> Result<(), ContractError> {
+    if let Some(retry_policy) = retry_policy {
+        if retry_policy.max_retries.is_zero() || retry_policy.max_retries.u64() > MAX_RETRIES {
+            return Err(ContractError::InvalidRetryPolicy { max: MAX_RETRIES });
+        }
+    }
+
+    Ok(())
+}
+
+// retry budget of a fresh job or of a new recurrence
+pub fn retries_left(retry_policy: &Option<RetryPolicy>) -> Uint64 {
+    match retry_policy {
+        None => Uint64::zero(),
+        Some(retry_policy) => retry_policy.max_retries,
+    }
+}
+
+pub fn should_retry(job: &Job, block: &BlockInfo) -> bool {
+    job.retry_policy.is_some() && !job.retries_left.is_zero() && !is_expired(job, block)
+}
+
+// eligibility of a job put back in the queue after a failed run at the given block
+pub fn retry_eligibility(
+    retry_policy: &Option<RetryPolicy>,
+    block: &BlockInfo,
+) -> Option<Expiration> {
+    retry_policy
+        .as_ref()
+        .map(|retry_policy| retry_policy.backoff.after(block))
+}
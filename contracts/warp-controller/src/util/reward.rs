@@ -0,0 +1,127 @@
+use crate::util::asset::job_reward_asset;
+use crate::util::limits::job_gas_limit;
+use crate::ContractError;
+use controller::account::AssetInfo;
+use controller::job::{GasAllowance, Job, RewardCurve, RewardRamp};
+use controller::Config;
+use cosmwasm_std::{Uint128, Uint64};
+
+pub fn validate_reward_curve(
+    reward_curve: &Option<RewardCurve>,
+    reward: Uint128,
+) -> Result<(), ContractError> {
+    let curve = match reward_curve {
+        None => return Ok(()),
+        Some(curve) => curve,
+    };
+
+    if curve.start_reward.is_zero() || curve.start_reward > reward {
+        return Err(ContractError::InvalidRewardCurve {});
+    }
+
+    match curve.ramp {
+        RewardRamp::Linear { duration } if duration.is_zero() => {
+            Err(ContractError::InvalidRewardCurve {})
+        }
+        RewardRamp::Exponential { doubling_period } if doubling_period.is_zero() => {
+            Err(ContractError::InvalidRewardCurve {})
+        }
+        _ => Ok(()),
+    }
+}
+
+// reward paid to a keeper executing the job at `now`, never more than the escrowed reward
+pub fn effective_reward(job: &Job, now: u64) -> Uint128 {
+    let curve = match &job.reward_curve {
+        None => return job.reward,
+        Some(curve) => curve,
+    };
+
+    let elapsed = now.saturating_sub(job.last_update_time.u64());
+
+    let value = match curve.ramp {
+        RewardRamp::Linear { duration } => {
+            linear_reward(curve.start_reward, job.reward, elapsed, duration)
+        }
+        RewardRamp::Exponential { doubling_period } => {
+            exponential_reward(curve.start_reward, elapsed, doubling_period)
+        }
+    };
+
+    match value {
+        Some(value) if value < job.reward => value,
+        _ => job.reward,
+    }
+}
+
+fn linear_reward(start: Uint128, max: Uint128, elapsed: u64, duration: Uint64) -> Option<Uint128> {
+    if elapsed >= duration.u64() {
+        return Some(max);
+    }
+
+    let growth = (max - start).multiply_ratio(elapsed, duration.u64());
+    start.checked_add(growth).ok()
+}
+
+// None once the value no longer fits, which is always past the cap
+fn exponential_reward(start: Uint128, elapsed: u64, doubling_period: Uint64) -> Option<Uint128> {
+    let doublings = elapsed / doubling_period.u64();
+    if doublings >= 128 {
+        return None;
+    }
+
+    let base = start.checked_mul(Uint128::new(1u128 << doublings)).ok()?;
+    let growth = base.multiply_ratio(elapsed % doubling_period.u64(), doubling_period.u64());
+    base.checked_add(growth).ok()
+}
+
+pub fn validate_gas_allowance(
+    config: &Config,
+    gas_allowance: &Option<GasAllowance>,
+    gas_limit: Option<Uint64>,
+) -> Result<(), ContractError> {
+    let gas_allowance = match gas_allowance {
+        None => return Ok(()),
+        Some(gas_allowance) => gas_allowance,
+    };
+
+    // the allowance is priced on the gas limit, so one is required
+    if gas_limit.is_none() && config.max_job_gas_limit.is_none() {
+        return Err(ContractError::InvalidGasAllowance {});
+    }
+
+    if gas_allowance.budget.is_zero() || gas_allowance.gas_price.is_zero() {
+        return Err(ContractError::InvalidGasAllowance {});
+    }
+
+    Ok(())
+}
+
+// gas budget escrowed by the job on top of its reward
+pub fn gas_budget(job: &Job) -> Uint128 {
+    match &job.gas_allowance {
+        None => Uint128::zero(),
+        Some(gas_allowance) => gas_allowance.budget,
+    }
+}
+
+// flat allowance for a run, the job's gas limit at its gas price capped by the budget
+pub fn gas_allowance(config: &Config, job: &Job) -> Uint128 {
+    match (&job.gas_allowance, job_gas_limit(config, job)) {
+        (Some(gas_allowance), Some(gas_limit)) => {
+            let cost = Uint128::from(gas_limit) * gas_allowance.gas_price;
+            cost.min(gas_allowance.budget)
+        }
+        _ => Uint128::zero(),
+    }
+}
+
+// eviction fee in the job's reward asset. a_min and a_max are fee_denom amounts, so a job paying
+// in another asset gives up the share of its reward that `a` is of the minimum reward
+pub fn eviction_fee(config: &Config, job: &Job, a: Uint128) -> Uint128 {
+    match job_reward_asset(config, job) {
+        AssetInfo::Native(denom) if denom == config.fee_denom => a,
+        _ if config.minimum_reward.is_zero() => Uint128::zero(),
+        _ => job.reward.multiply_ratio(a, config.minimum_reward),
+    }
+}
@@ -0,0 +1,236 @@
+use crate::ContractError;
+use controller::job::{JobExclusivity, JobSchedule};
+use cosmwasm_std::{BlockInfo, Timestamp};
+use cw_utils::{Duration, Expiration};
+
+const SECONDS_PER_MINUTE: u64 = 60;
+const SECONDS_PER_DAY: u64 = 86_400;
+// bounds the search for the next cron match, long enough to reach any 29th of february
+const MAX_CRON_SEARCH_DAYS: u64 = 366 * 8;
+
+// eligibility of a freshly created job, intervals run right away while cron waits for its first match
+pub fn initial_eligibility(
+    schedule: &Option<JobSchedule>,
+    block: &BlockInfo,
+) -> Result<Option<Expiration>, ContractError> {
+    match schedule {
+        None => Ok(None),
+        Some(JobSchedule::Interval(interval)) => {
+            validate_interval(interval)?;
+            Ok(None)
+        }
+        Some(JobSchedule::Cron(expr)) => Ok(Some(Expiration::AtTime(Timestamp::from_seconds(
+            next_cron_time(expr, block.time.seconds())?,
+        )))),
+    }
+}
+
+// eligibility of a job requeued after an execution at the given block
+pub fn next_eligibility(
+    schedule: &Option<JobSchedule>,
+    block: &BlockInfo,
+) -> Result<Option<Expiration>, ContractError> {
+    match schedule {
+        None => Ok(None),
+        Some(JobSchedule::Interval(interval)) => {
+            validate_interval(interval)?;
+            Ok(Some(interval.after(block)))
+        }
+        Some(JobSchedule::Cron(expr)) => Ok(Some(Expiration::AtTime(Timestamp::from_seconds(
+            next_cron_time(expr, block.time.seconds())?,
+        )))),
+    }
+}
+
+// eligibility of a job put back in the queue without running, a passed eligibility starts over
+pub fn requeued_eligibility(
+    schedule: &Option<JobSchedule>,
+    eligible_at: &Option<Expiration>,
+    block: &BlockInfo,
+) -> Result<Option<Expiration>, ContractError> {
+    match eligible_at {
+        Some(eligible_at) if !eligible_at.is_expired(block) => Ok(Some(*eligible_at)),
+        _ => initial_eligibility(schedule, block),
+    }
+}
+
+// end of the exclusivity window, which opens once the job becomes eligible
+pub fn exclusivity_end(
+    exclusivity: &Option<JobExclusivity>,
+    eligible_at: &Option<Expiration>,
+    block: &BlockInfo,
+) -> Option<Expiration> {
+    let window = match exclusivity {
+        None => return None,
+        Some(exclusivity) => exclusivity.window,
+    };
+
+    Some(match (window, eligible_at) {
+        (Duration::Time(seconds), Some(Expiration::AtTime(start))) if *start > block.time => {
+            Expiration::AtTime(start.plus_seconds(seconds))
+        }
+        (Duration::Height(blocks), Some(Expiration::AtHeight(start))) if *start > block.height => {
+            Expiration::AtHeight(start + blocks)
+        }
+        _ => window.after(block),
+    })
+}
+
+fn validate_interval(interval: &Duration) -> Result<(), ContractError> {
+    match interval {
+        Duration::Height(0) | Duration::Time(0) => Err(ContractError::InvalidSchedule {
+            msg: "Interval must be greater than zero.".to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+struct CronSpec {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    // standard cron semantics: when both day fields are restricted, either one matching is enough
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSpec {
+    fn parse(expr: &str) -> Result<CronSpec, ContractError> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(ContractError::InvalidSchedule {
+                msg: "Cron expression must have 5 fields.".to_string(),
+            });
+        }
+
+        let mut days_of_week = parse_cron_field(fields[4], 0, 7)?;
+        // both 0 and 7 stand for sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(CronSpec {
+            minutes: parse_cron_field(fields[0], 0, 59)?,
+            hours: parse_cron_field(fields[1], 0, 23)?,
+            days_of_month: parse_cron_field(fields[2], 1, 31)?,
+            months: parse_cron_field(fields[3], 1, 12)?,
+            days_of_week,
+            day_of_month_restricted: fields[2] != "*",
+            day_of_week_restricted: fields[4] != "*",
+        })
+    }
+
+    fn matches_day(&self, month: u64, day: u64, weekday: u64) -> bool {
+        if self.months & (1 << month) == 0 {
+            return false;
+        }
+
+        let dom = self.days_of_month & (1 << day) != 0;
+        let dow = self.days_of_week & (1 << weekday) != 0;
+
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+}
+
+fn parse_cron_field(field: &str, min: u64, max: u64) -> Result<u64, ContractError> {
+    let invalid = || ContractError::InvalidSchedule {
+        msg: format!("Invalid cron field: {}", field),
+    };
+
+    let mut mask = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            None => (part, 1),
+            Some((range, step)) => (range, step.parse::<u64>().map_err(|_| invalid())?),
+        };
+
+        if step == 0 {
+            return Err(invalid());
+        }
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                start.parse::<u64>().map_err(|_| invalid())?,
+                end.parse::<u64>().map_err(|_| invalid())?,
+            )
+        } else {
+            let start = range.parse::<u64>().map_err(|_| invalid())?;
+            // "5/15" means every 15 starting at 5
+            (start, if part.contains('/') { max } else { start })
+        };
+
+        if start < min || end > max || start > end {
+            return Err(invalid());
+        }
+
+        let mut value = start;
+        while value <= end {
+            mask |= 1 << value;
+            value += step;
+        }
+    }
+
+    Ok(mask)
+}
+
+// first minute strictly after `after` (unix seconds) matching the cron expression
+pub fn next_cron_time(expr: &str, after: u64) -> Result<u64, ContractError> {
+    let spec = CronSpec::parse(expr)?;
+
+    let start = (after / SECONDS_PER_MINUTE + 1) * SECONDS_PER_MINUTE;
+    let first_day = start / SECONDS_PER_DAY;
+
+    for day in first_day..first_day + MAX_CRON_SEARCH_DAYS {
+        let (_, month, day_of_month) = civil_from_days(day);
+        // 1970-01-01 was a thursday
+        let weekday = (day + 4) % 7;
+
+        if !spec.matches_day(month, day_of_month, weekday) {
+            continue;
+        }
+
+        let day_start = day * SECONDS_PER_DAY;
+        let from_minute = if day == first_day {
+            (start - day_start) / SECONDS_PER_MINUTE
+        } else {
+            0
+        };
+
+        for minute_of_day in from_minute..24 * 60 {
+            if spec.hours & (1 << (minute_of_day / 60)) != 0
+                && spec.minutes & (1 << (minute_of_day % 60)) != 0
+            {
+                return Ok(day_start + minute_of_day * SECONDS_PER_MINUTE);
+            }
+        }
+    }
+
+    Err(ContractError::InvalidSchedule {
+        msg: "Cron expression never matches.".to_string(),
+    })
+}
+
+// days since unix epoch to (year, month, day), see http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
@@ -0,0 +1,30 @@
+use controller::job::{Job, JobSummary};
+use cosmwasm_std::{Binary, Uint64};
+use sha2::{Digest, Sha256};
+
+pub fn job_summary(job: &Job, finished_at: Uint64) -> JobSummary {
+    JobSummary {
+        id: job.id,
+        owner: job.owner.clone(),
+        status: job.status.clone(),
+        reward: job.reward,
+        finished_at,
+        condition_hash: sha256(&job.condition),
+        msgs_hash: sha256(&job.msgs),
+        vars_hash: sha256(&job.vars),
+        branches_hash: branches_hash(job),
+    }
+}
+
+fn branches_hash(job: &Job) -> Option<Binary> {
+    if job.branches.is_empty() && job.else_msgs.is_none() {
+        return None;
+    }
+    serde_json_wasm::to_string(&(&job.branches, &job.else_msgs))
+        .ok()
+        .map(|branches| sha256(&branches))
+}
+
+fn sha256(data: &str) -> Binary {
+    Binary::from(Sha256::digest(data.as_bytes()).to_vec())
+}
@@ -1,7 +1,7 @@
-use crate::util::condition::{resolve_cond, resolve_query_expr};
+use crate::util::condition::{resolve_cond, resolve_query_expr, trace_cond};
 use crate::util::variable::{
-    apply_var_fn, has_duplicates, hydrate_msgs, hydrate_vars, msgs_valid, string_vars_in_vector,
-    vars_valid,
+    apply_var_fn, capture_outputs, has_duplicates, hydrate_msgs, hydrate_vars, msgs_valid,
+    string_vars_in_vector, vars_valid,
 };
 use crate::ContractError;
 use cosmwasm_std::{
@@ -9,14 +9,14 @@ use cosmwasm_std::{
     StdResult,
 };
 
-use resolver::condition::Condition;
+use resolver::condition::{Condition, ConditionTrace};
 use resolver::variable::{QueryExpr, Variable};
 use resolver::{
     ExecuteApplyVarFnMsg, ExecuteHydrateMsgsMsg, ExecuteHydrateVarsMsg, ExecuteMsg,
     ExecuteResolveConditionMsg, ExecuteSimulateQueryMsg, ExecuteValidateJobCreationMsg,
-    InstantiateMsg, MigrateMsg, QueryApplyVarFnMsg, QueryHydrateMsgsMsg, QueryHydrateVarsMsg,
-    QueryMsg, QueryResolveConditionMsg, QueryValidateJobCreationMsg, SimulateQueryMsg,
-    SimulateResponse,
+    InstantiateMsg, MigrateMsg, QueryApplyVarFnMsg, QueryCaptureOutputsMsg, QueryHydrateMsgsMsg,
+    QueryHydrateVarsMsg, QueryMsg, QueryResolveBranchMsg, QueryResolveConditionMsg,
+    QueryResolveConditionTraceMsg, QueryValidateJobCreationMsg, SimulateQueryMsg, SimulateResponse,
 };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -77,6 +77,8 @@ pub fn execute_validate_job_creation(
             terminate_condition: data.terminate_condition,
             vars: data.vars,
             msgs: data.msgs,
+            branches: data.branches,
+            else_msgs: data.else_msgs,
         },
     )?;
 
@@ -182,6 +184,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::QueryResolveCondition(data) => {
             to_binary(&query_resolve_condition(deps, env, data)?)
         }
+        QueryMsg::QueryResolveConditionTrace(data) => {
+            to_binary(&query_resolve_condition_trace(deps, env, data)?)
+        }
+        QueryMsg::QueryResolveBranch(data) => to_binary(&query_resolve_branch(deps, env, data)?),
+        QueryMsg::QueryCaptureOutputs(data) => to_binary(&query_capture_outputs(deps, env, data)?),
         QueryMsg::QueryApplyVarFn(data) => to_binary(&query_apply_var_fn(deps, env, data)?),
         QueryMsg::QueryHydrateMsgs(data) => to_binary(&query_hydrate_msgs(deps, env, data)?),
     }
@@ -201,6 +208,17 @@ fn query_validate_job_creation(
             StdError::generic_err(format!("Terminate condition input invalid: {}", e))
         })?;
     }
+    // branches are held to the same rules as the job's own condition and msgs
+    let mut conditions = vec![&data.condition];
+    let mut msgs = vec![&data.msgs];
+    for branch in &data.branches {
+        let _branch_condition: Condition = serde_json_wasm::from_str(&branch.condition)
+            .map_err(|e| StdError::generic_err(format!("Branch condition input invalid: {}", e)))?;
+        conditions.push(&branch.condition);
+        msgs.push(&branch.msgs);
+    }
+    msgs.extend(data.else_msgs.iter());
+
     let vars: Vec<Variable> = serde_json_wasm::from_str(&data.vars)
         .map_err(|e| StdError::generic_err(format!("Vars input invalid: {}", e)))?;
 
@@ -216,22 +234,26 @@ fn query_validate_job_creation(
         ));
     }
 
-    if !(string_vars_in_vector(&vars, &data.condition)
-        && string_vars_in_vector(&vars, &terminate_condition_str)
-        && string_vars_in_vector(&vars, &data.msgs))
+    if !(conditions
+        .iter()
+        .chain(msgs.iter())
+        .all(|s| string_vars_in_vector(&vars, s))
+        && string_vars_in_vector(&vars, &terminate_condition_str))
     {
         return Err(StdError::generic_err(
             ContractError::VariablesMissingFromVector {}.to_string(),
         ));
     }
 
-    if !msgs_valid(&data.msgs, &vars).map_err(|e| StdError::generic_err(e.to_string()))? {
-        return Err(StdError::generic_err(
-            ContractError::MsgError {
-                msg: "msgs are invalid".to_string(),
-            }
-            .to_string(),
-        ));
+    for msgs in msgs {
+        if !msgs_valid(msgs, &vars).map_err(|e| StdError::generic_err(e.to_string()))? {
+            return Err(StdError::generic_err(
+                ContractError::MsgError {
+                    msg: "msgs are invalid".to_string(),
+                }
+                .to_string(),
+            ));
+        }
     }
 
     Ok("".to_string())
@@ -260,6 +282,55 @@ fn query_resolve_condition(
     resolve_cond(deps, env, condition, &vars).map_err(|e| StdError::generic_err(e.to_string()))
 }
 
+// conditions after the first one that holds are not resolved
+fn query_resolve_branch(
+    deps: Deps,
+    env: Env,
+    data: QueryResolveBranchMsg,
+) -> StdResult<Option<u32>> {
+    let vars: Vec<Variable> =
+        serde_json_wasm::from_str(&data.vars).map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    for (index, condition) in data.conditions.iter().enumerate() {
+        let condition: Condition = serde_json_wasm::from_str(condition)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+        if resolve_cond(deps, env.clone(), condition, &vars)
+            .map_err(|e| StdError::generic_err(e.to_string()))?
+        {
+            return Ok(Some(index as u32));
+        }
+    }
+
+    Ok(None)
+}
+
+fn query_capture_outputs(
+    _deps: Deps,
+    _env: Env,
+    data: QueryCaptureOutputsMsg,
+) -> StdResult<String> {
+    let vars: Vec<Variable> =
+        serde_json_wasm::from_str(&data.vars).map_err(|e| StdError::generic_err(e.to_string()))?;
+    serde_json_wasm::to_string(
+        &capture_outputs(vars, &data.bindings, &data.events)
+            .map_err(|e| StdError::generic_err(e.to_string()))?,
+    )
+    .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+fn query_resolve_condition_trace(
+    deps: Deps,
+    env: Env,
+    data: QueryResolveConditionTraceMsg,
+) -> StdResult<ConditionTrace> {
+    let condition: Condition = serde_json_wasm::from_str(&data.condition)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let vars: Vec<Variable> =
+        serde_json_wasm::from_str(&data.vars).map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    trace_cond(deps, env, condition, &vars).map_err(|e| StdError::generic_err(e.to_string()))
+}
+
 fn query_apply_var_fn(deps: Deps, env: Env, data: QueryApplyVarFnMsg) -> StdResult<String> {
     let vars: Vec<Variable> =
         serde_json_wasm::from_str(&data.vars).map_err(|e| StdError::generic_err(e.to_string()))?;
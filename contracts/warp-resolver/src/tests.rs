@@ -1,17 +1,27 @@
 use schemars::_serde_json::json;
 
+use controller::job::{OutputBinding, OutputSource};
+
 use crate::util::variable::{hydrate_msgs, hydrate_vars};
 
 use cosmwasm_std::{testing::mock_env, WasmQuery};
-use cosmwasm_std::{to_binary, BankQuery, Binary, ContractResult, CosmosMsg, OwnedDeps, WasmMsg};
+use cosmwasm_std::{
+    to_binary, BankQuery, Binary, ContractResult, CosmosMsg, Event, OwnedDeps, WasmMsg,
+};
 
 use crate::contract::query;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{mock_info, MockApi, MockQuerier, MockStorage};
-use cosmwasm_std::{from_slice, Empty, Querier, QueryRequest, SystemError, SystemResult};
+use cosmwasm_std::{
+    from_binary, from_slice, Empty, Querier, QueryRequest, SystemError, SystemResult,
+};
 
+use resolver::condition::{ConditionTrace, ExprTrace};
 use resolver::variable::{QueryExpr, QueryVariable, StaticVariable, Variable, VariableKind};
-use resolver::{QueryMsg, QueryValidateJobCreationMsg};
+use resolver::{
+    QueryCaptureOutputsMsg, QueryMsg, QueryResolveBranchMsg, QueryResolveConditionTraceMsg,
+    QueryValidateJobCreationMsg,
+};
 use std::marker::PhantomData;
 
 #[test]
@@ -24,6 +34,8 @@ fn test() {
         terminate_condition: None,
         vars: "[{\"query\":{\"kind\":\"decimal\",\"name\":\"return_amount\",\"init_fn\":{\"query\":{\"wasm\":{\"smart\":{\"msg\":\"eyJzaW11bGF0aW9uIjp7Im9mZmVyX2Fzc2V0Ijp7ImFtb3VudCI6IjEwMDAwMDAiLCJpbmZvIjp7Im5hdGl2ZV90b2tlbiI6eyJkZW5vbSI6ImliYy9CMzUwNEUwOTI0NTZCQTYxOENDMjhBQzY3MUE3MUZCMDhDNkNBMEZEMEJFN0M4QTVCNUEzRTJERDkzM0NDOUU0In19fX19\",\"contract_addr\":\"terra1fd68ah02gr2y8ze7tm9te7m70zlmc7vjyyhs6xlhsdmqqcjud4dql4wpxr\"}}},\"selector\":\"$.return_amount\"},\"reinitialize\":false,\"encode\":false}}]".to_string(),
         msgs: "[{\"wasm\":{\"execute\":{\"contract_addr\":\"terra1fd68ah02gr2y8ze7tm9te7m70zlmc7vjyyhs6xlhsdmqqcjud4dql4wpxr\",\"msg\":\"eyJzd2FwIjp7Im9mZmVyX2Fzc2V0Ijp7ImluZm8iOnsibmF0aXZlX3Rva2VuIjp7ImRlbm9tIjoiaWJjL0IzNTA0RTA5MjQ1NkJBNjE4Q0MyOEFDNjcxQTcxRkIwOEM2Q0EwRkQwQkU3QzhBNUI1QTNFMkREOTMzQ0M5RTQifX0sImFtb3VudCI6IjEwMDAwMDAifSwibWF4X3NwcmVhZCI6IjAuNSIsImJlbGllZl9wcmljZSI6IjAuNjEwMzg3MzI3MzgyNDYzODE2In19\",\"funds\":[{\"denom\":\"ibc/B3504E092456BA618CC28AC671A71FB08C6CA0FD0BE7C8A5B5A3E2DD933CC9E4\",\"amount\":\"1000000\"}]}}}]".to_string(),
+        branches: vec![],
+        else_msgs: None,
     };
     let obj = serde_json_wasm::to_string(&vec!["{\"wasm\":{\"execute\":{\"contract_addr\":\"terra1fd68ah02gr2y8ze7tm9te7m70zlmc7vjyyhs6xlhsdmqqcjud4dql4wpxr\",\"msg\":\"eyJzd2FwIjp7Im9mZmVyX2Fzc2V0Ijp7ImluZm8iOnsibmF0aXZlX3Rva2VuIjp7ImRlbm9tIjoiaWJjL0IzNTA0RTA5MjQ1NkJBNjE4Q0MyOEFDNjcxQTcxRkIwOEM2Q0EwRkQwQkU3QzhBNUI1QTNFMkREOTMzQ0M5RTQifX0sImFtb3VudCI6IjEwMDAwMDAifSwibWF4X3NwcmVhZCI6IjAuNSIsImJlbGllZl9wcmljZSI6IjAuNjEwMzg3MzI3MzgyNDYzODE2In19\",\"funds\":[{\"denom\":\"ibc/B3504E092456BA618CC28AC671A71FB08C6CA0FD0BE7C8A5B5A3E2DD933CC9E4\",\"amount\":\"1000000\"}]}}}"]).unwrap();
 
@@ -32,6 +44,8 @@ fn test() {
         terminate_condition: None,
         vars: "[{\"query\":{\"kind\":\"decimal\",\"name\":\"return_amount\",\"init_fn\":{\"query\":{\"wasm\":{\"smart\":{\"msg\":\"eyJzaW11bGF0aW9uIjp7Im9mZmVyX2Fzc2V0Ijp7ImFtb3VudCI6IjEwMDAwMDAiLCJpbmZvIjp7Im5hdGl2ZV90b2tlbiI6eyJkZW5vbSI6ImliYy9CMzUwNEUwOTI0NTZCQTYxOENDMjhBQzY3MUE3MUZCMDhDNkNBMEZEMEJFN0M4QTVCNUEzRTJERDkzM0NDOUU0In19fX19\",\"contract_addr\":\"terra1fd68ah02gr2y8ze7tm9te7m70zlmc7vjyyhs6xlhsdmqqcjud4dql4wpxr\"}}},\"selector\":\"$.return_amount\"},\"reinitialize\":false,\"encode\":false}}]".to_string(),
         msgs: obj.clone(),
+        branches: vec![],
+        else_msgs: None,
     };
 
     println!("{}", serde_json_wasm::to_string(&obj).unwrap());
@@ -49,6 +63,63 @@ fn test_vars() {
     let _new_str = test_msg.replace("\"$WARPVAR.test\"", "\"input\"");
 }
 
+#[test]
+fn test_resolve_condition_trace() {
+    let deps = mock_dependencies();
+    let env = mock_env();
+
+    let vars = vec![Variable::Static(StaticVariable {
+        kind: VariableKind::Uint,
+        name: "amount".to_string(),
+        encode: false,
+        value: "20".to_string(),
+        update_fn: None,
+    })];
+
+    let condition = format!(
+        "{{\"and\":[{{\"expr\":{{\"uint\":{{\"left\":{{\"ref\":\"$warp.variable.amount\"}},\"op\":\"gt\",\"right\":{{\"simple\":\"10\"}}}}}}}},{{\"not\":{{\"expr\":{{\"block_height\":{{\"comparator\":\"{}\",\"op\":\"lt\"}}}}}}}},{{\"expr\":{{\"bool\":\"$warp.variable.amount\"}}}}]}}",
+        env.block.height + 1
+    );
+
+    let trace: ConditionTrace = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryResolveConditionTrace(QueryResolveConditionTraceMsg {
+                condition,
+                vars: serde_json_wasm::to_string(&vars).unwrap(),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    // evaluation stops at the negated block height expression, the bool expression is never reached
+    assert_eq!(
+        trace,
+        ConditionTrace::And {
+            conditions: vec![
+                ConditionTrace::Expr(ExprTrace {
+                    left: "20".to_string(),
+                    op: "gt".to_string(),
+                    right: "10".to_string(),
+                    result: true,
+                }),
+                ConditionTrace::Not {
+                    condition: Box::new(ConditionTrace::Expr(ExprTrace {
+                        left: env.block.height.to_string(),
+                        op: "lt".to_string(),
+                        right: (env.block.height + 1).to_string(),
+                        result: true,
+                    })),
+                    result: false,
+                },
+            ],
+            result: false,
+        }
+    );
+}
+
 pub fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
     let custom_querier: WasmMockQuerier = WasmMockQuerier::new(MockQuerier::new(&[]));
 
@@ -119,6 +190,133 @@ impl WasmMockQuerier {
     }
 }
 
+#[test]
+fn test_resolve_branch() {
+    let deps = mock_dependencies();
+    let env = mock_env();
+
+    let vars = serde_json_wasm::to_string(&vec![Variable::Static(StaticVariable {
+        kind: VariableKind::Uint,
+        name: "amount".to_string(),
+        encode: false,
+        value: "20".to_string(),
+        update_fn: None,
+    })])
+    .unwrap();
+
+    let amount_cond = |op: &str, right: &str| {
+        format!(
+            "{{\"expr\":{{\"uint\":{{\"left\":{{\"ref\":\"$warp.variable.amount\"}},\"op\":\"{}\",\"right\":{{\"simple\":\"{}\"}}}}}}}}",
+            op, right
+        )
+    };
+
+    let resolve_branch = |conditions: Vec<String>| -> Option<u32> {
+        from_binary(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::QueryResolveBranch(QueryResolveBranchMsg {
+                    conditions,
+                    vars: vars.clone(),
+                }),
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // take profit above 30, stop loss below 25
+    assert_eq!(
+        resolve_branch(vec![amount_cond("gt", "30"), amount_cond("lt", "25")]),
+        Some(1)
+    );
+    assert_eq!(
+        resolve_branch(vec![amount_cond("gt", "10"), amount_cond("lt", "25")]),
+        Some(0)
+    );
+    assert_eq!(
+        resolve_branch(vec![amount_cond("gt", "30"), amount_cond("lt", "15")]),
+        None
+    );
+}
+
+#[test]
+fn test_capture_outputs() {
+    let deps = mock_dependencies();
+    let env = mock_env();
+
+    let static_var = |name: &str| {
+        Variable::Static(StaticVariable {
+            kind: VariableKind::Uint,
+            name: name.to_string(),
+            encode: false,
+            value: "0".to_string(),
+            update_fn: None,
+        })
+    };
+
+    let capture = |bindings: Vec<OutputBinding>| {
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryCaptureOutputs(QueryCaptureOutputsMsg {
+                vars: serde_json_wasm::to_string(&vec![static_var("received"), static_var("id")])
+                    .unwrap(),
+                bindings,
+                events: vec![Event::new("wasm")
+                    .add_attribute("action", "swap")
+                    .add_attribute("return_amount", "1500")
+                    .add_attribute("position", r#"{"id":7}"#)],
+            }),
+        )
+    };
+
+    let vars: String = from_binary(
+        &capture(vec![
+            OutputBinding {
+                var: "received".to_string(),
+                source: OutputSource::EventAttribute {
+                    event_type: "wasm".to_string(),
+                    key: "return_amount".to_string(),
+                },
+                selector: None,
+            },
+            OutputBinding {
+                var: "id".to_string(),
+                source: OutputSource::EventAttribute {
+                    event_type: "wasm".to_string(),
+                    key: "position".to_string(),
+                },
+                selector: Some("$.id".to_string()),
+            },
+        ])
+        .unwrap(),
+    )
+    .unwrap();
+
+    let vars: Vec<Variable> = serde_json_wasm::from_str(&vars).unwrap();
+    let values: Vec<String> = vars
+        .into_iter()
+        .map(|var| match var {
+            Variable::Static(v) => v.value,
+            _ => panic!("unexpected variable"),
+        })
+        .collect();
+    assert_eq!(values, vec!["1500".to_string(), "7".to_string()]);
+
+    // a missing attribute fails the capture
+    assert!(capture(vec![OutputBinding {
+        var: "received".to_string(),
+        source: OutputSource::EventAttribute {
+            event_type: "wasm".to_string(),
+            key: "offer_amount".to_string(),
+        },
+        selector: None,
+    }])
+    .is_err());
+}
+
 #[test]
 fn test_hydrate_vars_nested_variables_binary_json() {
     let deps = mock_dependencies();
@@ -1,6 +1,7 @@
 use crate::util::path::resolve_path;
 use crate::util::variable::get_var;
 use crate::ContractError;
+use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{
     to_vec, ContractResult, Decimal256, Deps, Env, StdError, SystemResult, Uint256,
 };
@@ -8,8 +9,9 @@ use cw_storage_plus::KeyDeserialize;
 use json_codec_wasm::ast::Ref;
 use json_codec_wasm::Decoder;
 use resolver::condition::{
-    BlockExpr, Condition, DecimalFnOp, Expr, GenExpr, IntFnOp, NumEnvValue, NumExprOp,
-    NumExprValue, NumFnValue, NumOp, NumValue, StringOp, TimeExpr, TimeOp, Value,
+    BlockExpr, Condition, ConditionTrace, DecimalFnOp, Expr, ExprTrace, GenExpr, IntFnOp,
+    NumEnvValue, NumExprOp, NumExprValue, NumFnValue, NumOp, NumValue, StringOp, TimeExpr, TimeOp,
+    Value,
 };
 use resolver::variable::{QueryExpr, Variable};
 use std::str::FromStr;
@@ -42,6 +44,134 @@ pub fn resolve_cond(
     }
 }
 
+// same evaluation as resolve_cond, keeping every evaluated node and its resolved values
+pub fn trace_cond(
+    deps: Deps,
+    env: Env,
+    cond: Condition,
+    vars: &Vec<Variable>,
+) -> Result<ConditionTrace, ContractError> {
+    match cond {
+        Condition::And(conds) => {
+            let mut conditions = vec![];
+            for cond in conds {
+                let trace = trace_cond(deps, env.clone(), *cond, vars)?;
+                let result = trace.result();
+                conditions.push(trace);
+                if !result {
+                    return Ok(ConditionTrace::And {
+                        conditions,
+                        result: false,
+                    });
+                }
+            }
+            Ok(ConditionTrace::And {
+                conditions,
+                result: true,
+            })
+        }
+        Condition::Or(conds) => {
+            let mut conditions = vec![];
+            for cond in conds {
+                let trace = trace_cond(deps, env.clone(), *cond, vars)?;
+                let result = trace.result();
+                conditions.push(trace);
+                if result {
+                    return Ok(ConditionTrace::Or {
+                        conditions,
+                        result: true,
+                    });
+                }
+            }
+            Ok(ConditionTrace::Or {
+                conditions,
+                result: false,
+            })
+        }
+        Condition::Not(cond) => {
+            let trace = trace_cond(deps, env, *cond, vars)?;
+            Ok(ConditionTrace::Not {
+                result: !trace.result(),
+                condition: Box::new(trace),
+            })
+        }
+        Condition::Expr(expr) => Ok(ConditionTrace::Expr(trace_expr(deps, env, *expr, vars)?)),
+    }
+}
+
+pub fn trace_expr(
+    deps: Deps,
+    env: Env,
+    expr: Expr,
+    vars: &Vec<Variable>,
+) -> Result<ExprTrace, ContractError> {
+    let (left, op, right, result) = match expr {
+        Expr::String(expr) => {
+            let left = resolve_value_string(deps, env.clone(), expr.left, vars)?;
+            let right = resolve_value_string(deps, env.clone(), expr.right, vars)?;
+            let op = op_string(&expr.op)?;
+            let result = resolve_str_op(deps, env, left.clone(), right.clone(), expr.op);
+            (left, op, right, result)
+        }
+        Expr::Uint(expr) => {
+            let left = resolve_num_value_uint(deps, env.clone(), expr.left, vars)?;
+            let right = resolve_num_value_uint(deps, env.clone(), expr.right, vars)?;
+            let op = op_string(&expr.op)?;
+            let result = resolve_uint_op(deps, env, left, right, expr.op);
+            (left.to_string(), op, right.to_string(), result)
+        }
+        Expr::Int(expr) => {
+            let left = resolve_num_value_int(deps, env.clone(), expr.left, vars)?;
+            let right = resolve_num_value_int(deps, env.clone(), expr.right, vars)?;
+            let op = op_string(&expr.op)?;
+            let result = resolve_int_op(deps, env, left, right, expr.op);
+            (left.to_string(), op, right.to_string(), result)
+        }
+        Expr::Decimal(expr) => {
+            let left = resolve_num_value_decimal(deps, env.clone(), expr.left, vars)?;
+            let right = resolve_num_value_decimal(deps, env.clone(), expr.right, vars)?;
+            let op = op_string(&expr.op)?;
+            let result = resolve_decimal_op(deps, env, left, right, expr.op);
+            (left.to_string(), op, right.to_string(), result)
+        }
+        Expr::Timestamp(expr) => (
+            env.block.time.seconds().to_string(),
+            op_string(&expr.op)?,
+            expr.comparator.to_string(),
+            resolve_timestamp_expr(deps, env, expr)?,
+        ),
+        Expr::BlockHeight(expr) => (
+            env.block.height.to_string(),
+            op_string(&expr.op)?,
+            expr.comparator.to_string(),
+            resolve_block_expr(deps, env, expr)?,
+        ),
+        Expr::Bool(expr) => {
+            let result = resolve_ref_bool(deps, env, expr, vars)?;
+            (
+                result.to_string(),
+                "eq".to_string(),
+                true.to_string(),
+                result,
+            )
+        }
+    };
+
+    Ok(ExprTrace {
+        left,
+        op,
+        right,
+        result,
+    })
+}
+
+// operators are shown the way they are written in a condition
+fn op_string<T: Serialize>(op: &T) -> Result<String, ContractError> {
+    Ok(serde_json_wasm::to_string(op)?
+        .trim_matches('"')
+        .to_string())
+}
+
 pub fn resolve_expr(
     deps: Deps,
     env: Env,
@@ -489,31 +619,21 @@ pub fn resolve_string_expr(
     expr: GenExpr<Value<String>, StringOp>,
     vars: &Vec<Variable>,
 ) -> Result<bool, ContractError> {
-    match (expr.left, expr.right) {
-        (Value::Simple(left), Value::Simple(right)) => {
-            Ok(resolve_str_op(deps, env, left, right, expr.op))
-        }
-        (Value::Simple(left), Value::Ref(right)) => Ok(resolve_str_op(
-            deps,
-            env.clone(),
-            left,
-            resolve_ref_string(deps, env, right, vars)?,
-            expr.op,
-        )),
-        (Value::Ref(left), Value::Simple(right)) => Ok(resolve_str_op(
-            deps,
-            env.clone(),
-            resolve_ref_string(deps, env, left, vars)?,
-            right,
-            expr.op,
-        )),
-        (Value::Ref(left), Value::Ref(right)) => Ok(resolve_str_op(
-            deps,
-            env.clone(),
-            resolve_ref_string(deps, env.clone(), left, vars)?,
-            resolve_ref_string(deps, env, right, vars)?,
-            expr.op,
-        )),
+    let left = resolve_value_string(deps, env.clone(), expr.left, vars)?;
+    let right = resolve_value_string(deps, env.clone(), expr.right, vars)?;
+
+    Ok(resolve_str_op(deps, env, left, right, expr.op))
+}
+
+fn resolve_value_string(
+    deps: Deps,
+    env: Env,
+    value: Value<String>,
+    vars: &Vec<Variable>,
+) -> Result<String, ContractError> {
+    match value {
+        Value::Simple(value) => Ok(value),
+        Value::Ref(r) => resolve_ref_string(deps, env, r, vars),
     }
 }
 
@@ -3,15 +3,18 @@ use crate::util::condition::{
     resolve_query_expr_bool, resolve_query_expr_decimal, resolve_query_expr_int,
     resolve_query_expr_string, resolve_query_expr_uint, resolve_ref_bool,
 };
+use crate::util::path::resolve_path;
 use crate::ContractError;
 use cosmwasm_schema::serde::de::DeserializeOwned;
 use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{
-    Binary, CosmosMsg, Decimal256, Deps, Env, QueryRequest, Uint128, Uint256, WasmQuery,
+    Binary, CosmosMsg, Decimal256, Deps, Env, Event, QueryRequest, Uint128, Uint256, WasmQuery,
 };
+use json_codec_wasm::ast::Ref;
+use json_codec_wasm::{Decoder, Encoder};
 use std::str::FromStr;
 
-use controller::job::{ExternalInput, JobStatus};
+use controller::job::{ExternalInput, JobStatus, OutputBinding, OutputSource};
 use resolver::variable::{QueryExpr, UpdateFnValue, Variable, VariableKind};
 
 pub fn hydrate_vars(
@@ -576,6 +579,61 @@ pub fn msgs_valid(msgs: &str, vars: &Vec<Variable>) -> Result<bool, ContractErro
     Ok(true)
 }
 
+pub fn capture_outputs(
+    vars: Vec<Variable>,
+    bindings: &[OutputBinding],
+    events: &[Event],
+) -> Result<Vec<Variable>, ContractError> {
+    let mut vars = vars;
+
+    for binding in bindings {
+        let output = match &binding.source {
+            OutputSource::EventAttribute { event_type, key } => events
+                .iter()
+                .filter(|event| event.ty == *event_type)
+                .flat_map(|event| event.attributes.iter())
+                .find(|attr| attr.key == *key)
+                .map(|attr| attr.value.clone())
+                .ok_or(ContractError::HydrationError {
+                    msg: format!("Output attribute not found: {}.{}", event_type, key),
+                })?,
+        };
+
+        let value = match &binding.selector {
+            None => output,
+            Some(selector) => {
+                let json = Decoder::default(output.chars()).decode()?;
+                json_string(resolve_path(Ref::new(&json), selector.clone())?)?
+            }
+        };
+
+        match vars.iter_mut().find(|var| get_var_name(var) == binding.var) {
+            Some(Variable::Static(v)) => v.value = value,
+            _ => {
+                return Err(ContractError::HydrationError {
+                    msg: format!("Output variable must be static: {}", binding.var),
+                })
+            }
+        }
+    }
+
+    Ok(vars)
+}
+
+// strings are bound without their quotes, everything else as json
+fn json_string(r: Ref) -> Result<String, ContractError> {
+    if let Some(s) = r.string() {
+        return Ok(s.to_string());
+    }
+
+    let json = r.value().ok_or(ContractError::ResolveError {})?;
+    let mut encoder = Encoder::new(Vec::new());
+    encoder
+        .encode(json)
+        .map_err(|_| ContractError::SerializationError {})?;
+    String::from_utf8(encoder.into_writer()).map_err(|_| ContractError::SerializationError {})
+}
+
 pub fn apply_var_fn(
     deps: Deps,
     env: Env,
@@ -1069,7 +1127,7 @@ pub fn get_var(name: String, vars: &Vec<Variable>) -> Result<&Variable, Contract
     Err(ContractError::VariableNotFound { name })
 }
 
-pub fn has_duplicates(vars: &Vec<Variable>) -> bool {
+pub fn has_duplicates(vars: &[Variable]) -> bool {
     for i in 0..vars.len() {
         for j in i..vars.len() {
             if i != j {
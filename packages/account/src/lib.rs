@@ -1,4 +1,4 @@
-use controller::account::{AssetInfo, Fund};
+use controller::account::{AssetInfo, Fund, MsgPolicy};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, CosmosMsg};
 use schemars::JsonSchema;
@@ -20,6 +20,7 @@ pub struct InstantiateMsg {
 #[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     Generic(GenericMsg),
+    GenericWithPolicy(GenericWithPolicyMsg),
     WithdrawAssets(WithdrawAssetsMsg),
     IbcTransfer(IbcTransferMsg),
 }
@@ -29,6 +30,18 @@ pub struct GenericMsg {
     pub msgs: Vec<CosmosMsg>,
 }
 
+// msgs run one by one as submessages, the response data is a MsgExecutionSummary
+#[cw_serde]
+pub struct GenericWithPolicyMsg {
+    pub msgs: Vec<PolicyMsg>,
+}
+
+#[cw_serde]
+pub struct PolicyMsg {
+    pub msg: CosmosMsg,
+    pub policy: MsgPolicy,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, prost::Message)]
 pub struct Coin {
     #[prost(string, tag = "1")]
@@ -81,3 +81,34 @@ pub enum AssetInfo {
     Cw20(Addr),
     Cw721(Addr, String),
 }
+
+// how a failing msg affects the rest of a policy execution
+#[cw_serde]
+pub enum MsgPolicy {
+    // reverts the whole execution
+    Required,
+    // recorded as failed, the following msgs still run
+    Optional,
+    // recorded as failed, the following msgs are skipped
+    StopOnError,
+}
+
+#[cw_serde]
+pub enum MsgStatus {
+    Executed,
+    Failed,
+    Skipped,
+}
+
+#[cw_serde]
+pub struct MsgResult {
+    pub index: u32,
+    pub status: MsgStatus,
+    pub error: Option<String>,
+}
+
+// set as the response data of a policy execution
+#[cw_serde]
+pub struct MsgExecutionSummary {
+    pub results: Vec<MsgResult>,
+}
@@ -1,6 +1,7 @@
-use crate::account::AssetInfo;
+use crate::account::{AssetInfo, MsgPolicy, MsgResult};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128, Uint64};
+use cosmwasm_std::{Addr, Binary, CosmosMsg, Decimal, Uint128, Uint64};
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum_macros::Display;
@@ -36,6 +37,130 @@ pub struct Job {
     pub requeue_on_evict: bool,
     pub reward: Uint128,
     pub assets_to_withdraw: Vec<AssetInfo>,
+    pub schedule: Option<JobSchedule>,
+    // job cannot be executed before this point, set from the schedule
+    pub eligible_at: Option<Expiration>,
+    pub max_executions: Option<Uint64>,
+    pub expires_at: Option<Expiration>,
+    // number of runs so far, carried over when a recurring job is requeued
+    #[serde(default)]
+    pub executions: Uint64,
+    // asset the reward and fees are paid in, fee_denom if unset
+    pub reward_asset: Option<AssetInfo>,
+    // upstream jobs that must be finished before this job can run
+    #[serde(default)]
+    pub dependencies: Vec<JobDependency>,
+    // gas limit for the job's submessage, falls back to the config maximum
+    pub gas_limit: Option<Uint64>,
+    pub exclusivity: Option<JobExclusivity>,
+    // only the exclusive keepers may execute before this point, set whenever the job is queued
+    pub exclusive_until: Option<Expiration>,
+    // keeper reward grows over time up to `reward`, which is escrowed in full
+    pub reward_curve: Option<RewardCurve>,
+    pub gas_allowance: Option<GasAllowance>,
+    // block time the job left the pending queue for good, unset on jobs finished before it was recorded
+    pub finished_at: Option<Uint64>,
+    // id of the first job of a recurring series, carried over to every requeued job
+    #[serde(default)]
+    pub series_id: Uint64,
+    // a recurring job that keeps its id across runs instead of being requeued under a new one
+    #[serde(default)]
+    pub stable_id: bool,
+    // why the job ended up failed, unset for every other status
+    pub failure_reason: Option<JobFailureReason>,
+    pub retry_policy: Option<RetryPolicy>,
+    // retries left before a failed run finishes the job
+    #[serde(default)]
+    pub retries_left: Uint64,
+    // per msg policies, msgs run all or nothing when unset
+    pub msg_policies: Option<Vec<MsgPolicy>>,
+    // per msg outcome of the last run with msg policies
+    pub msg_results: Option<Vec<MsgResult>>,
+    // tried in order after condition and msgs, the first branch whose condition holds is sent
+    #[serde(default)]
+    pub branches: Vec<JobBranch>,
+    // sent when no condition holds, the job is never left waiting when set
+    pub else_msgs: Option<String>,
+    // outputs of the last successful run written into vars, before update functions are applied
+    #[serde(default)]
+    pub output_bindings: Vec<OutputBinding>,
+}
+
+#[cw_serde]
+pub struct JobBranch {
+    pub condition: String,
+    pub msgs: String,
+}
+
+// the account forwards no response data of the msgs it runs, so outputs are read from events
+#[cw_serde]
+pub enum OutputSource {
+    // attribute of the first event of the given type carrying the key
+    EventAttribute { event_type: String, key: String },
+}
+
+// binds an output of a run to a static variable
+#[cw_serde]
+pub struct OutputBinding {
+    pub var: String,
+    pub source: OutputSource,
+    // resolve_path selector into the output parsed as json, the raw output is bound when unset
+    pub selector: Option<String>,
+}
+
+#[cw_serde]
+pub enum JobSchedule {
+    // fixed spacing between runs, counted from the previous execution
+    Interval(Duration),
+    // "minute hour day-of-month month day-of-week", evaluated in UTC
+    Cron(String),
+}
+
+#[cw_serde]
+pub struct RewardCurve {
+    pub start_reward: Uint128,
+    pub ramp: RewardRamp,
+}
+
+// elapsed time is measured in seconds since the job's last_update_time
+#[cw_serde]
+pub enum RewardRamp {
+    // grows evenly from the start reward to the job reward over the duration
+    Linear { duration: Uint64 },
+    // doubles every period, rising linearly within each period, until it reaches the job reward
+    Exponential { doubling_period: Uint64 },
+}
+
+// flat gas allowance paid to the keeper on top of the reward, priced on the job's gas limit.
+// Replies carry no gas usage in CosmWasm 1.x, so actual usage is not measured
+#[cw_serde]
+pub struct GasAllowance {
+    // escrowed next to the reward in the reward asset, what the allowance leaves goes back to the
+    // owner after each run
+    pub budget: Uint128,
+    // price per unit of the job's gas limit
+    pub gas_price: Decimal,
+}
+
+#[cw_serde]
+pub struct JobExclusivity {
+    pub keepers: Vec<Addr>,
+    // counted from when the job becomes eligible
+    pub window: Duration,
+}
+
+#[cw_serde]
+pub struct JobDependency {
+    pub job_id: Uint64,
+    pub required_status: DependencyStatus,
+}
+
+#[cw_serde]
+pub enum DependencyStatus {
+    Executed,
+    Failed,
+    // any finished status, including cancelled, evicted and expired
+    Any,
 }
 
 #[cw_serde]
@@ -51,6 +176,10 @@ pub enum JobStatus {
     Failed,
     Cancelled,
     Evicted,
+    Expired,
+    Paused,
+    // a recurring job that ran its max executions
+    Completed,
 }
 
 #[cw_serde]
@@ -66,6 +195,43 @@ pub struct CreateJobMsg {
     pub requeue_on_evict: bool,
     pub reward: Uint128,
     pub assets_to_withdraw: Option<Vec<AssetInfo>>,
+    pub schedule: Option<JobSchedule>,
+    pub max_executions: Option<Uint64>,
+    pub expires_at: Option<Expiration>,
+    pub reward_asset: Option<AssetInfo>,
+    pub dependencies: Option<Vec<JobDependency>>,
+    pub gas_limit: Option<Uint64>,
+    pub exclusivity: Option<JobExclusivity>,
+    pub reward_curve: Option<RewardCurve>,
+    pub gas_allowance: Option<GasAllowance>,
+    pub stable_id: Option<bool>,
+    pub retry_policy: Option<RetryPolicy>,
+    pub msg_policies: Option<Vec<MsgPolicy>>,
+    pub branches: Option<Vec<JobBranch>>,
+    pub else_msgs: Option<String>,
+    pub output_bindings: Option<Vec<OutputBinding>>,
+}
+
+#[cw_serde]
+pub struct CreateJobsMsg {
+    pub jobs: Vec<CreateJobMsg>,
+}
+
+#[cw_serde]
+pub struct PauseJobMsg {
+    pub id: Uint64,
+}
+
+#[cw_serde]
+pub struct ResumeJobMsg {
+    pub id: Uint64,
+}
+
+#[cw_serde]
+pub struct PruneFinishedJobsMsg {
+    // finished job id to continue scanning from
+    pub start_after: Option<Uint64>,
+    pub limit: u32,
 }
 
 #[cw_serde]
@@ -80,6 +246,15 @@ pub struct UpdateJobMsg {
     pub description: Option<String>,
     pub labels: Option<Vec<String>>,
     pub added_reward: Option<Uint128>,
+    pub condition: Option<String>,
+    pub terminate_condition: Option<String>,
+    pub msgs: Option<String>,
+    pub vars: Option<String>,
+    pub recurring: Option<bool>,
+    pub requeue_on_evict: Option<bool>,
+    // replaces every extra branch, an empty list removes them
+    pub branches: Option<Vec<JobBranch>>,
+    pub else_msgs: Option<String>,
 }
 
 #[cw_serde]
@@ -110,26 +285,76 @@ pub struct QueryJobsMsg {
     pub active: Option<bool>,
     pub owner: Option<Addr>,
     pub name: Option<String>,
+    pub name_prefix: Option<String>,
+    pub labels: Option<LabelFilter>,
     pub job_status: Option<JobStatus>,
     pub condition_status: Option<bool>,
-    pub start_after: Option<JobIndex>,
+    pub recurring: Option<bool>,
+    // inclusive bounds
+    pub min_reward: Option<Uint128>,
+    pub max_reward: Option<Uint128>,
+    pub updated_after: Option<Uint64>,
+    pub updated_before: Option<Uint64>,
+    // defaults to reward, descending
+    pub sort_by: Option<JobSortKey>,
+    pub order: Option<SortOrder>,
+    // next_cursor of the previous page
+    pub start_after: Option<JobCursor>,
     pub limit: Option<u32>,
 }
 
 #[cw_serde]
-pub struct JobIndex {
-    pub _0: Uint128,
-    pub _1: Uint64,
+pub enum JobSortKey {
+    Reward,
+    Id,
+    UpdateTime,
+}
+
+#[cw_serde]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[cw_serde]
+pub enum LabelFilter {
+    // job has at least one of the labels
+    Any(Vec<String>),
+    // job has every one of the labels
+    All(Vec<String>),
+}
+
+#[cw_serde]
+pub struct QueryJobDependenciesMsg {
+    pub id: Uint64,
+}
+
+#[cw_serde]
+pub struct QueryJobRevisionsMsg {
+    pub id: Uint64,
+    pub start_after: Option<Uint64>,
+    pub limit: Option<u32>,
+}
+
+// position of a job in the sort order, the value of its sort key with the id breaking ties
+#[cw_serde]
+pub struct JobCursor {
+    pub value: Uint128,
+    pub id: Uint64,
 }
 
 impl QueryJobsMsg {
     pub fn valid_query(&self) -> bool {
-        (self.ids.is_some() as u8
-            + (self.owner.is_some()
-                || self.name.is_some()
-                || self.job_status.is_some()
-                || self.condition_status.is_some()) as u8)
-            <= 1
+        let reward_range_valid = match (self.min_reward, self.max_reward) {
+            (Some(min), Some(max)) => min <= max,
+            _ => true,
+        };
+        let time_range_valid = match (self.updated_after, self.updated_before) {
+            (Some(after), Some(before)) => after <= before,
+            _ => true,
+        };
+
+        reward_range_valid && time_range_valid
     }
 }
 
@@ -141,10 +366,167 @@ pub struct QueryResolveJobConditionMsg {
 #[cw_serde]
 pub struct JobResponse {
     pub job: Job,
+    // what a keeper executing the job right now would be paid
+    pub effective_reward: Uint128,
 }
 
 #[cw_serde]
 pub struct JobsResponse {
     pub jobs: Vec<Job>,
-    pub total_count: usize,
+    // effective reward of each job, in the same order as jobs
+    pub effective_rewards: Vec<Uint128>,
+    // number of jobs matching the filters across all pages, read from the job counters. Unset for
+    // filters the counters don't track and while MigrateJobIndexes has not finished after an upgrade
+    pub total_count: Option<usize>,
+    // set when there may be more jobs after this page
+    pub next_cursor: Option<JobCursor>,
+}
+
+#[cw_serde]
+pub struct JobRevision {
+    pub revision: Uint64,
+    pub updated_at: Uint64,
+    pub changes: Vec<JobFieldChange>,
+}
+
+#[cw_serde]
+pub struct JobFieldChange {
+    pub field: String,
+    // json encoded values
+    pub old_value: String,
+    pub new_value: String,
+}
+
+#[cw_serde]
+pub struct JobRevisionsResponse {
+    pub revisions: Vec<JobRevision>,
+}
+
+#[cw_serde]
+pub struct JobDependencyNode {
+    pub id: Uint64,
+    pub status: JobStatus,
+    pub dependencies: Vec<JobDependency>,
+}
+
+#[cw_serde]
+pub struct JobDependenciesResponse {
+    // the queried job and every job it transitively depends on
+    pub nodes: Vec<JobDependencyNode>,
+    // whether the direct dependencies of the queried job are met
+    pub satisfied: bool,
+}
+
+// what is kept of a finished job once it is pruned
+#[cw_serde]
+pub struct JobSummary {
+    pub id: Uint64,
+    pub owner: Addr,
+    pub status: JobStatus,
+    pub reward: Uint128,
+    pub finished_at: Uint64,
+    // sha256 of the job's condition, msgs and vars as they were stored
+    pub condition_hash: Binary,
+    pub msgs_hash: Binary,
+    pub vars_hash: Binary,
+    // sha256 of the job's extra branches and else msgs, unset for a job with neither
+    #[serde(default)]
+    pub branches_hash: Option<Binary>,
+}
+
+// a failed run puts the job back in the queue once the backoff has passed
+#[cw_serde]
+pub struct RetryPolicy {
+    pub max_retries: Uint64,
+    pub backoff: Duration,
+}
+
+#[cw_serde]
+pub enum JobFailureReason {
+    ConditionInvalid {
+        msg: String,
+    },
+    HydrationError {
+        msg: String,
+    },
+    // codespace and code are read from the submessage error when present
+    SubmsgError {
+        module: Option<String>,
+        code: Option<u32>,
+        msg: String,
+    },
+    OutOfGas,
+    InsufficientFunds {
+        msg: String,
+    },
+}
+
+// one execution of a job in a recurring series
+#[cw_serde]
+pub struct JobRun {
+    pub run: Uint64,
+    pub job_id: Uint64,
+    pub status: JobStatus,
+    pub keeper: Addr,
+    pub height: Uint64,
+    pub time: Uint64,
+    pub reward_paid: Uint128,
+    pub error: Option<String>,
+}
+
+#[cw_serde]
+pub struct QueryJobHistoryMsg {
+    pub series_id: Uint64,
+    pub start_after: Option<Uint64>,
+    pub limit: Option<u32>,
+}
+
+#[cw_serde]
+pub struct JobHistoryResponse {
+    pub runs: Vec<JobRun>,
+}
+
+#[cw_serde]
+pub struct SimulateJobMsg {
+    pub job: CreateJobMsg,
+    pub external_inputs: Option<Vec<ExternalInput>>,
+}
+
+// one node of the condition tree, path lists the child indexes leading to it and is empty for the root
+#[cw_serde]
+pub struct ConditionBreakdown {
+    pub path: String,
+    pub condition: String,
+    pub result: Option<bool>,
+    pub error: Option<String>,
+}
+
+// amounts moved out of the owner's account when the job is created
+#[cw_serde]
+pub struct JobFees {
+    pub reward_asset: AssetInfo,
+    pub reward: Uint128,
+    pub creation_fee: Uint128,
+    pub gas_budget: Uint128,
+    pub total: Uint128,
+}
+
+#[cw_serde]
+pub struct SimulateJobResponse {
+    pub vars: String,
+    pub condition: Option<bool>,
+    pub condition_error: Option<String>,
+    pub condition_breakdown: Vec<ConditionBreakdown>,
+    pub msgs: Vec<CosmosMsg>,
+    pub fees: JobFees,
+}
+
+#[cw_serde]
+pub struct QueryJobSummaryMsg {
+    pub id: Uint64,
+}
+
+#[cw_serde]
+pub struct JobSummaryResponse {
+    pub summary: JobSummary,
 }
@@ -0,0 +1,66 @@
+use crate::account::AssetInfo;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128, Uint64};
+
+#[cw_serde]
+pub struct Keeper {
+    pub address: Addr,
+    // bonded in fee_denom
+    pub stake: Uint128,
+    // unbonded stake waiting out the unbonding period, slashed like the bonded stake
+    pub unbonding: Uint128,
+    // withdrawable once the block time reaches it
+    pub unbonding_release_at: Option<Uint64>,
+    pub executed: Uint64,
+    pub failed: Uint64,
+    pub evicted: Uint64,
+    pub slashed: Uint128,
+    pub rewards_earned: Vec<KeeperReward>,
+}
+
+#[cw_serde]
+pub struct KeeperReward {
+    pub asset: AssetInfo,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct BondKeeperMsg {}
+
+#[cw_serde]
+pub struct UnbondKeeperMsg {
+    // unbonds the whole stake if unset
+    pub amount: Option<Uint128>,
+}
+
+// withdraws unbonding stake once its period has passed
+#[cw_serde]
+pub struct WithdrawKeeperStakeMsg {}
+
+#[cw_serde]
+pub struct QueryKeeperMsg {
+    pub address: String,
+}
+
+#[cw_serde]
+pub struct QueryKeepersMsg {
+    pub start_after: Option<KeeperIndex>,
+    pub limit: Option<u32>,
+}
+
+// keepers are ranked by executed jobs, ties broken by address
+#[cw_serde]
+pub struct KeeperIndex {
+    pub executed: Uint64,
+    pub address: String,
+}
+
+#[cw_serde]
+pub struct KeeperResponse {
+    pub keeper: Keeper,
+}
+
+#[cw_serde]
+pub struct KeepersResponse {
+    pub keepers: Vec<Keeper>,
+}
@@ -1,15 +1,24 @@
 use crate::account::{
-    AccountResponse, AccountsResponse, CreateAccountMsg, QueryAccountMsg, QueryAccountsMsg,
+    AccountResponse, AccountsResponse, AssetInfo, CreateAccountMsg, QueryAccountMsg,
+    QueryAccountsMsg,
 };
 use crate::job::{
-    CreateJobMsg, DeleteJobMsg, EvictJobMsg, ExecuteJobMsg, JobResponse, JobsResponse, QueryJobMsg,
-    QueryJobsMsg, UpdateJobMsg,
+    CreateJobMsg, CreateJobsMsg, DeleteJobMsg, EvictJobMsg, ExecuteJobMsg, JobDependenciesResponse,
+    JobHistoryResponse, JobResponse, JobRevisionsResponse, JobSummaryResponse, JobsResponse,
+    PauseJobMsg, PruneFinishedJobsMsg, QueryJobDependenciesMsg, QueryJobHistoryMsg, QueryJobMsg,
+    QueryJobRevisionsMsg, QueryJobSummaryMsg, QueryJobsMsg, ResumeJobMsg, SimulateJobMsg,
+    SimulateJobResponse, UpdateJobMsg,
+};
+use crate::keeper::{
+    BondKeeperMsg, KeeperResponse, KeepersResponse, QueryKeeperMsg, QueryKeepersMsg,
+    UnbondKeeperMsg, WithdrawKeeperStakeMsg,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128, Uint64};
 
 pub mod account;
 pub mod job;
+pub mod keeper;
 
 //objects
 #[cw_serde]
@@ -32,6 +41,20 @@ pub struct Config {
     pub a_min: Uint128,
     // maximum length of queue modifier for evictions
     pub q_max: Uint64,
+    // native denoms and cw20 tokens accepted as job rewards besides fee_denom
+    pub reward_assets: Vec<AssetInfo>,
+    // upper bound for the gas a job's msgs may use, unbounded if unset
+    pub max_job_gas_limit: Option<Uint64>,
+    // stake a keeper needs to execute jobs, registration is optional when this and the slash percentage are zero
+    pub keeper_min_stake: Uint128,
+    // share of the stake taken when a keeper executes a job whose condition is false or invalid
+    pub keeper_slash_percentage: Uint64,
+    // seconds unbonded keeper stake stays slashable before it can be withdrawn
+    pub keeper_unbonding_period: Uint64,
+    // seconds a finished job is kept in full before it can be pruned to a summary, pruning is off if unset
+    pub finished_job_retention: Option<Uint64>,
+    // paid to keepers per pruned job out of the job owner's account, in fee_denom
+    pub prune_bounty: Uint128,
 }
 
 #[cw_serde]
@@ -57,24 +80,41 @@ pub struct InstantiateMsg {
     pub a_max: Uint128,
     pub a_min: Uint128,
     pub q_max: Uint64,
+    pub reward_assets: Option<Vec<AssetInfo>>,
+    pub max_job_gas_limit: Option<Uint64>,
+    pub keeper_min_stake: Option<Uint128>,
+    pub keeper_slash_percentage: Option<Uint64>,
+    pub keeper_unbonding_period: Option<Uint64>,
+    pub finished_job_retention: Option<Uint64>,
+    pub prune_bounty: Option<Uint128>,
 }
 
 //execute
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     CreateJob(CreateJobMsg),
+    CreateJobs(CreateJobsMsg),
     DeleteJob(DeleteJobMsg),
     UpdateJob(UpdateJobMsg),
     ExecuteJob(ExecuteJobMsg),
     EvictJob(EvictJobMsg),
+    PauseJob(PauseJobMsg),
+    ResumeJob(ResumeJobMsg),
+    PruneFinishedJobs(PruneFinishedJobsMsg),
 
     CreateAccount(CreateAccountMsg),
 
+    BondKeeper(BondKeeperMsg),
+    UnbondKeeper(UnbondKeeperMsg),
+    WithdrawKeeperStake(WithdrawKeeperStakeMsg),
+
     UpdateConfig(UpdateConfigMsg),
 
     MigrateAccounts(MigrateAccountsMsg),
     MigratePendingJobs(MigrateJobsMsg),
     MigrateFinishedJobs(MigrateJobsMsg),
+    MigrateJobIndexes(MigrateJobsMsg),
 }
 
 #[cw_serde]
@@ -89,6 +129,17 @@ pub struct UpdateConfigMsg {
     pub a_max: Option<Uint128>,
     pub a_min: Option<Uint128>,
     pub q_max: Option<Uint64>,
+    pub reward_assets: Option<Vec<AssetInfo>>,
+    pub max_job_gas_limit: Option<Uint64>,
+    // removes the cap on job gas limits, cannot be combined with max_job_gas_limit
+    pub clear_max_job_gas_limit: Option<bool>,
+    pub keeper_min_stake: Option<Uint128>,
+    pub keeper_slash_percentage: Option<Uint64>,
+    pub keeper_unbonding_period: Option<Uint64>,
+    pub finished_job_retention: Option<Uint64>,
+    // keeps finished jobs forever again, cannot be combined with finished_job_retention
+    pub clear_finished_job_retention: Option<bool>,
+    pub prune_bounty: Option<Uint128>,
 }
 
 #[cw_serde]
@@ -107,17 +158,33 @@ pub struct MigrateJobsMsg {
 //query
 #[derive(QueryResponses)]
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum QueryMsg {
     #[returns(JobResponse)]
     QueryJob(QueryJobMsg),
     #[returns(JobsResponse)]
     QueryJobs(QueryJobsMsg),
+    #[returns(JobDependenciesResponse)]
+    QueryJobDependencies(QueryJobDependenciesMsg),
+    #[returns(JobRevisionsResponse)]
+    QueryJobRevisions(QueryJobRevisionsMsg),
+    #[returns(JobSummaryResponse)]
+    QueryJobSummary(QueryJobSummaryMsg),
+    #[returns(JobHistoryResponse)]
+    QueryJobHistory(QueryJobHistoryMsg),
+    #[returns(SimulateJobResponse)]
+    SimulateJob(SimulateJobMsg),
 
     #[returns(AccountResponse)]
     QueryAccount(QueryAccountMsg),
     #[returns(AccountsResponse)]
     QueryAccounts(QueryAccountsMsg),
 
+    #[returns(KeeperResponse)]
+    QueryKeeper(QueryKeeperMsg),
+    #[returns(KeepersResponse)]
+    QueryKeepers(QueryKeepersMsg),
+
     #[returns(ConfigResponse)]
     QueryConfig(QueryConfigMsg),
 }
@@ -133,6 +200,7 @@ pub struct ConfigResponse {
 
 //migrate//{"resolver_address":"terra1a8dxkrapwj4mkpfnrv7vahd0say0lxvd0ft6qv","warp_account_code_id":"10081"}
 #[cw_serde]
+// job counts are unavailable after the migration until MigrateJobIndexes has rewritten every job
 pub struct MigrateMsg {
     pub warp_account_code_id: Uint64,
     pub resolver_address: String,
@@ -11,6 +11,44 @@ pub enum Condition {
     Expr(Box<Expr>),
 }
 
+// how a condition was evaluated, children are listed up to the one that decided an and/or
+#[cw_serde]
+pub enum ConditionTrace {
+    And {
+        conditions: Vec<ConditionTrace>,
+        result: bool,
+    },
+    Or {
+        conditions: Vec<ConditionTrace>,
+        result: bool,
+    },
+    Not {
+        condition: Box<ConditionTrace>,
+        result: bool,
+    },
+    Expr(ExprTrace),
+}
+
+impl ConditionTrace {
+    pub fn result(&self) -> bool {
+        match self {
+            ConditionTrace::And { result, .. }
+            | ConditionTrace::Or { result, .. }
+            | ConditionTrace::Not { result, .. } => *result,
+            ConditionTrace::Expr(expr) => expr.result,
+        }
+    }
+}
+
+// both sides of an expression resolved to their final values
+#[cw_serde]
+pub struct ExprTrace {
+    pub left: String,
+    pub op: String,
+    pub right: String,
+    pub result: bool,
+}
+
 #[cw_serde]
 pub struct GenExpr<Type, Op> {
     pub left: Type,
@@ -1,9 +1,10 @@
 pub mod condition;
 pub mod variable;
 
-use controller::job::{ExternalInput, JobStatus};
+use crate::condition::ConditionTrace;
+use controller::job::{ExternalInput, JobBranch, JobStatus, OutputBinding};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, QueryRequest};
+use cosmwasm_std::{CosmosMsg, Event, QueryRequest};
 #[cw_serde]
 pub struct InstantiateMsg {}
 
@@ -28,6 +29,12 @@ pub enum QueryMsg {
     QueryHydrateVars(QueryHydrateVarsMsg),
     #[returns(bool)]
     QueryResolveCondition(QueryResolveConditionMsg),
+    #[returns(ConditionTrace)]
+    QueryResolveConditionTrace(QueryResolveConditionTraceMsg),
+    #[returns(Option<u32>)]
+    QueryResolveBranch(QueryResolveBranchMsg),
+    #[returns(String)]
+    QueryCaptureOutputs(QueryCaptureOutputsMsg),
     #[returns(String)]
     QueryApplyVarFn(QueryApplyVarFnMsg),
     #[returns(Vec<CosmosMsg>)]
@@ -72,6 +79,9 @@ pub struct ExecuteValidateJobCreationMsg {
     pub terminate_condition: Option<String>,
     pub vars: String,
     pub msgs: String,
+    #[serde(default)]
+    pub branches: Vec<JobBranch>,
+    pub else_msgs: Option<String>,
 }
 
 #[cw_serde]
@@ -80,6 +90,9 @@ pub struct QueryValidateJobCreationMsg {
     pub terminate_condition: Option<String>,
     pub vars: String,
     pub msgs: String,
+    #[serde(default)]
+    pub branches: Vec<JobBranch>,
+    pub else_msgs: Option<String>,
 }
 
 #[cw_serde]
@@ -100,6 +113,27 @@ pub struct QueryResolveConditionMsg {
     pub vars: String,
 }
 
+#[cw_serde]
+pub struct QueryResolveConditionTraceMsg {
+    pub condition: String,
+    pub vars: String,
+}
+
+// index of the first condition that holds
+#[cw_serde]
+pub struct QueryResolveBranchMsg {
+    pub conditions: Vec<String>,
+    pub vars: String,
+}
+
+// vars with the outputs of a run written into their bound variables
+#[cw_serde]
+pub struct QueryCaptureOutputsMsg {
+    pub vars: String,
+    pub bindings: Vec<OutputBinding>,
+    pub events: Vec<Event>,
+}
+
 #[cw_serde]
 pub struct QueryApplyVarFnMsg {
     pub vars: String,